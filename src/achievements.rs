@@ -0,0 +1,152 @@
+use crate::stats::Stats;
+use crate::GameState;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+pub struct Achievement {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Checked on the finished game, after it was added to the stats.
+    pub unlocked: fn(&GameState, &Stats) -> bool,
+}
+
+pub const ACHIEVEMENTS: [Achievement; 5] = [
+    Achievement {
+        id: "first-win",
+        name: "First Win",
+        description: "Win a game",
+        unlocked: |game_state, _| game_state.won(),
+    },
+    Achievement {
+        id: "flawless",
+        name: "Flawless",
+        description: "Win without entering an invalid guess",
+        unlocked: |game_state, _| game_state.won() && game_state.invalid_attempts == 0,
+    },
+    Achievement {
+        id: "two-guesses",
+        name: "Mind Reader",
+        description: "Solve a word in two guesses or less",
+        unlocked: |game_state, _| game_state.won() && game_state.turns() <= 2,
+    },
+    Achievement {
+        id: "daily-week",
+        name: "Regular",
+        description: "Solve the daily puzzle seven days in a row",
+        unlocked: |_, stats| stats.daily.current >= 7,
+    },
+    Achievement {
+        id: "veteran",
+        name: "Veteran",
+        description: "Play 100 games",
+        unlocked: |_, stats| stats.played() >= 100,
+    },
+];
+
+/// Persisted ids of the unlocked achievements.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Achievements {
+    pub unlocked: BTreeSet<String>,
+}
+
+impl Achievements {
+    /// Unlocks the achievements earned by the finished game and returns the new ones.
+    pub fn evaluate(&mut self, game_state: &GameState, stats: &Stats) -> Vec<&'static Achievement> {
+        let unlocked: Vec<&'static Achievement> = ACHIEVEMENTS
+            .iter()
+            .filter(|achievement| !self.unlocked.contains(achievement.id))
+            .filter(|achievement| (achievement.unlocked)(game_state, stats))
+            .collect();
+        for achievement in &unlocked {
+            self.unlocked.insert(achievement.id.to_string());
+        }
+        unlocked
+    }
+
+    pub fn load(path: &Path) -> io::Result<Achievements> {
+        match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Achievements::default()),
+            Err(error) => Err(error),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        fs::write(path, content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn achievement(id: &str) -> &'static Achievement {
+        ACHIEVEMENTS.iter().find(|a| a.id == id).unwrap()
+    }
+
+    fn game(word: &str, guesses: &[&str]) -> GameState {
+        let words: Vec<String> = ["hello", "jolly", "world", "crane"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let mut game_state = GameState::new(word.to_string(), words, false);
+        for guess in guesses {
+            for c in guess.chars() {
+                game_state.add_char(c);
+            }
+            game_state.confirm();
+        }
+        game_state
+    }
+
+    #[test]
+    fn test_solved_in_two() {
+        let stats = Stats::default();
+        let unlocked = achievement("two-guesses").unlocked;
+        assert!(unlocked(&game("hello", &["jolly", "hello"]), &stats));
+        assert!(!unlocked(
+            &game("hello", &["jolly", "world", "hello"]),
+            &stats
+        ));
+        assert!(!unlocked(&game("hello", &["jolly"]), &stats));
+
+        // a forfeited turn counts as a guess
+        let mut game_state = game("hello", &["jolly"]);
+        game_state.forfeit_turn(std::time::Instant::now());
+        game_state.guess("hello".to_string()).unwrap();
+        assert!(game_state.won());
+        assert!(!unlocked(&game_state, &stats));
+    }
+
+    #[test]
+    fn test_flawless_requires_valid_guesses() {
+        let stats = Stats::default();
+        let unlocked = achievement("flawless").unlocked;
+        assert!(unlocked(&game("hello", &["world", "hello"]), &stats));
+        assert!(!unlocked(&game("hello", &["abcde", "hello"]), &stats));
+    }
+
+    #[test]
+    fn test_achievements_unlock_once() {
+        let mut achievements = Achievements::default();
+        let game_state = game("hello", &["hello"]);
+        let stats = Stats::default();
+        let unlocked: Vec<&str> = achievements
+            .evaluate(&game_state, &stats)
+            .iter()
+            .map(|a| a.id)
+            .collect();
+        assert_eq!(unlocked, vec!["first-win", "flawless", "two-guesses"]);
+        assert!(achievements.evaluate(&game_state, &stats).is_empty());
+    }
+}
@@ -0,0 +1,102 @@
+use crate::solver::{self, Decision};
+
+/// Result of the solver playing a single answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordResult {
+    pub word: String,
+    /// None if the solver didn't find the word
+    pub guesses: Option<usize>,
+}
+
+/// Lets the solver play every answer, its guesses are added to `log` if one is given.
+pub fn run(
+    answers: &[String],
+    max_tries: usize,
+    mut log: Option<&mut Vec<Decision>>,
+) -> Vec<WordResult> {
+    let opener = match solver::best_guess(answers) {
+        Some(opener) => opener.clone(),
+        None => return Vec::new(),
+    };
+    answers
+        .iter()
+        .map(|word| WordResult {
+            word: word.clone(),
+            guesses: solver::simulate(word, answers, &opener, max_tries, log.as_deref_mut()),
+        })
+        .collect()
+}
+
+/// The `n` words which took the most guesses, unsolved words first.
+pub fn hardest(results: &[WordResult], n: usize) -> Vec<&WordResult> {
+    let mut sorted: Vec<&WordResult> = results.iter().collect();
+    sorted.sort_by(|a, b| {
+        let key = |result: &WordResult| result.guesses.unwrap_or(usize::MAX);
+        key(b).cmp(&key(a)).then_with(|| a.word.cmp(&b.word))
+    });
+    sorted.truncate(n);
+    sorted
+}
+
+pub fn report(results: &[WordResult], n: usize) -> String {
+    let solved: Vec<usize> = results.iter().filter_map(|result| result.guesses).collect();
+    let average = if solved.is_empty() {
+        0.0
+    } else {
+        solved.iter().sum::<usize>() as f64 / solved.len() as f64
+    };
+    let mut report = format!(
+        "Solved {}/{} words, {:.2} guesses on average\nHardest words:",
+        solved.len(),
+        results.len(),
+        average
+    );
+    for result in hardest(results, n) {
+        match result.guesses {
+            Some(guesses) => report.push_str(&format!("\n{} {}", result.word, guesses)),
+            None => report.push_str(&format!("\n{} unsolved", result.word)),
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn test_hard_word_ranks_above_easy_word() {
+        let answers = words(&[
+            "bills", "fills", "hills", "kills", "mills", "pills", "zebra",
+        ]);
+        let results = run(&answers, 6, None);
+        let ranking: Vec<&str> = hardest(&results, answers.len())
+            .iter()
+            .map(|result| result.word.as_str())
+            .collect();
+        let easy = ranking.iter().position(|w| *w == "zebra").unwrap();
+        assert!(ranking[0].ends_with("ills"));
+        assert!(easy > 0);
+        assert!(results.iter().all(|result| result.guesses.is_some()));
+    }
+
+    #[test]
+    fn test_unsolved_words_first() {
+        let results = vec![
+            WordResult {
+                word: "hello".to_string(),
+                guesses: Some(6),
+            },
+            WordResult {
+                word: "world".to_string(),
+                guesses: None,
+            },
+        ];
+        assert_eq!(hardest(&results, 1)[0].word, "world");
+        assert!(report(&results, 2).contains("world unsolved"));
+    }
+}
@@ -0,0 +1,110 @@
+/// Characters used to draw the board grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BorderStyle {
+    pub name: &'static str,
+    pub vertical: char,
+    pub horizontal: char,
+    /// left, inner and right junction of the line above the first row
+    pub top: Option<[char; 3]>,
+    /// junctions of the lines between rows
+    pub middle: Option<[char; 3]>,
+    /// junctions of the line below the last row
+    pub bottom: Option<[char; 3]>,
+}
+
+impl BorderStyle {
+    pub fn ascii() -> BorderStyle {
+        BorderStyle {
+            name: "ascii",
+            vertical: '|',
+            horizontal: '-',
+            top: Some(['-', '-', '-']),
+            middle: Some(['-', '-', '-']),
+            bottom: None,
+        }
+    }
+
+    pub fn unicode() -> BorderStyle {
+        BorderStyle {
+            name: "unicode",
+            vertical: '│',
+            horizontal: '─',
+            top: Some(['┌', '┬', '┐']),
+            middle: Some(['├', '┼', '┤']),
+            bottom: Some(['└', '┴', '┘']),
+        }
+    }
+
+    pub fn none() -> BorderStyle {
+        BorderStyle {
+            name: "none",
+            vertical: ' ',
+            horizontal: ' ',
+            top: None,
+            middle: None,
+            bottom: None,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<BorderStyle> {
+        match name {
+            "ascii" => Some(BorderStyle::ascii()),
+            "unicode" => Some(BorderStyle::unicode()),
+            "none" => Some(BorderStyle::none()),
+            _ => None,
+        }
+    }
+
+    /// Builds a horizontal line for a board `width` cells wide.
+    pub fn line(&self, junctions: [char; 3], width: usize) -> String {
+        let mut line = String::new();
+        line.push(junctions[0]);
+        for x in 0..width {
+            line.push(self.horizontal);
+            line.push(if x + 1 == width {
+                junctions[2]
+            } else {
+                junctions[1]
+            });
+        }
+        line
+    }
+
+    /// Line drawn above row `y`, the first row uses the top junctions.
+    pub fn line_above(&self, y: usize, width: usize) -> Option<String> {
+        let junctions = if y == 0 { self.top } else { self.middle };
+        junctions.map(|junctions| self.line(junctions, width))
+    }
+
+    pub fn line_below_last(&self, width: usize) -> Option<String> {
+        self.bottom.map(|junctions| self.line(junctions, width))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_line() {
+        let border = BorderStyle::ascii();
+        assert_eq!(border.line_above(0, 3), Some("-------".to_string()));
+        assert_eq!(border.line_below_last(3), None);
+    }
+
+    #[test]
+    fn test_unicode_lines() {
+        let border = BorderStyle::unicode();
+        assert_eq!(border.line_above(0, 2), Some("┌─┬─┐".to_string()));
+        assert_eq!(border.line_above(1, 2), Some("├─┼─┤".to_string()));
+        assert_eq!(border.line_below_last(2), Some("└─┴─┘".to_string()));
+    }
+
+    #[test]
+    fn test_none_has_no_lines() {
+        let border = BorderStyle::none();
+        assert_eq!(border.line_above(0, 5), None);
+        assert_eq!(border.line_above(3, 5), None);
+        assert_eq!(border.line_below_last(5), None);
+    }
+}
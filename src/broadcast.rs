@@ -0,0 +1,102 @@
+//! Plain text copy of the board for stream overlays.
+//!
+//! The file is rewritten after every render and looks like
+//!
+//! ```text
+//! rustle 2/6 playing
+//! jolly .YGG.
+//! hello GGGGG
+//! > hel
+//! ```
+//!
+//! Feedback uses the same letters as the multiplayer protocol: G for a hit,
+//! Y for a letter in the wrong place and `.` for a miss.
+
+use crate::protocol::encode_hits;
+use crate::{row_letters, GameState};
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+
+pub fn board_text(game_state: &GameState) -> String {
+    let status = if game_state.won() {
+        "won"
+    } else if game_state.is_over() {
+        "lost"
+    } else {
+        "playing"
+    };
+    let mut text = format!(
+        "rustle {}/{} {}\n",
+        game_state.turns(),
+        game_state.max_tries,
+        status
+    );
+    for row in 0..game_state.turns() {
+        let (guess, hits) = match game_state.guess_in_row(row) {
+            Some(i) => (game_state.guesses[i].clone(), game_state.get_guess_hits(i)),
+            None => row_letters(game_state, row),
+        };
+        text.push_str(&format!("{} {}\n", guess, encode_hits(&hits)));
+    }
+    if !game_state.is_over() {
+        text.push_str(&format!("> {}\n", game_state.current_guess));
+    }
+    text
+}
+
+/// Replaces the content of `path`, which may also be a named pipe.
+///
+/// The file is opened non-blocking so a pipe without a reader doesn't stall
+/// the game; the update is simply dropped in that case.
+pub fn write(path: &Path, game_state: &GameState) -> io::Result<()> {
+    let file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .custom_flags(libc::O_NONBLOCK)
+        .open(path);
+    match file {
+        Ok(mut file) => file.write_all(board_text(game_state).as_bytes()),
+        Err(error) if error.raw_os_error() == Some(libc::ENXIO) => Ok(()),
+        Err(error) => Err(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_broadcast_file_after_guess() {
+        let words = vec!["hello".to_string(), "jolly".to_string()];
+        let mut game_state = GameState::new("hello".to_string(), words, false);
+        for c in "jolly".chars() {
+            game_state.add_char(c);
+        }
+        game_state.confirm();
+        game_state.add_char('h');
+
+        let path = std::env::temp_dir().join(format!("rustle-broadcast-{}", std::process::id()));
+        write(&path, &game_state).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(content, "rustle 1/6 playing\njolly .YGG.\n> h\n");
+    }
+
+    #[test]
+    fn test_broadcast_pipe_without_reader() {
+        use std::ffi::CString;
+        use std::os::unix::ffi::OsStrExt;
+
+        let game_state = GameState::new("hello".to_string(), vec!["hello".to_string()], false);
+        let path =
+            std::env::temp_dir().join(format!("rustle-broadcast-fifo-{}", std::process::id()));
+        let c_path = CString::new(path.as_os_str().as_bytes()).unwrap();
+        assert_eq!(unsafe { libc::mkfifo(c_path.as_ptr(), 0o600) }, 0);
+        let result = write(&path, &game_state);
+        std::fs::remove_file(&path).unwrap();
+        assert!(result.is_ok());
+    }
+}
@@ -0,0 +1,236 @@
+//! Short codes which let a friend replay the same puzzle.
+//!
+//! A code is the URL safe base64 encoding (without padding) of
+//!
+//! | bytes | content                                             |
+//! |-------|-----------------------------------------------------|
+//! | 1     | version, currently 1                                |
+//! | 1     | 0 for a random answer, 1 for a daily puzzle         |
+//! | 4     | index of the answer or number of the daily puzzle   |
+//! | 1     | word length                                         |
+//! | 1     | guesses needed, 0 if the word wasn't found          |
+//! | 1     | checksum of the bytes before, last in every version |
+
+use crate::GameState;
+
+const VERSION: u8 = 1;
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+/// bytes of a code
+const LENGTH: usize = 9;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Puzzle {
+    /// index into the answers of the word list
+    Answer(usize),
+    /// number of the daily puzzle
+    Daily(usize),
+}
+
+/// Puzzle and result of a shared game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChallengeSpec {
+    pub puzzle: Puzzle,
+    pub length: usize,
+    /// guesses the sender needed, None if they didn't find the word
+    pub guesses: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChallengeError {
+    Malformed,
+    UnsupportedVersion(u8),
+    Checksum,
+}
+
+impl std::fmt::Display for ChallengeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ChallengeError::Malformed => write!(f, "Challenge code is malformed"),
+            ChallengeError::UnsupportedVersion(version) => {
+                write!(f, "Challenge code version {} is not supported", version)
+            }
+            ChallengeError::Checksum => write!(f, "Challenge code is corrupted"),
+        }
+    }
+}
+
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes
+        .iter()
+        .fold(0u8, |sum, byte| sum.wrapping_mul(31).wrapping_add(*byte))
+}
+
+fn encode_base64(bytes: &[u8]) -> String {
+    let mut bits = 0u32;
+    let mut count = 0;
+    let mut code = String::new();
+    for byte in bytes {
+        bits = (bits << 8) | *byte as u32;
+        count += 8;
+        while count >= 6 {
+            count -= 6;
+            code.push(ALPHABET[((bits >> count) & 63) as usize] as char);
+        }
+    }
+    if count > 0 {
+        code.push(ALPHABET[((bits << (6 - count)) & 63) as usize] as char);
+    }
+    code
+}
+
+fn decode_base64(code: &str) -> Option<Vec<u8>> {
+    let mut bits = 0u32;
+    let mut count = 0;
+    let mut bytes = Vec::new();
+    for c in code.bytes() {
+        let value = ALPHABET.iter().position(|a| *a == c)? as u32;
+        bits = (bits << 6) | value;
+        count += 6;
+        if count >= 8 {
+            count -= 8;
+            bytes.push((bits >> count) as u8);
+        }
+    }
+    Some(bytes)
+}
+
+pub fn encode(spec: &ChallengeSpec) -> String {
+    let (kind, index) = match spec.puzzle {
+        Puzzle::Answer(index) => (0, index),
+        Puzzle::Daily(index) => (1, index),
+    };
+    let mut bytes = vec![VERSION, kind];
+    bytes.extend((index as u32).to_be_bytes());
+    bytes.push(spec.length as u8);
+    bytes.push(spec.guesses.unwrap_or(0) as u8);
+    bytes.push(checksum(&bytes));
+    encode_base64(&bytes)
+}
+
+pub fn decode_challenge(code: &str) -> Result<ChallengeSpec, ChallengeError> {
+    let bytes = decode_base64(code.trim()).ok_or(ChallengeError::Malformed)?;
+    // the checksum comes last in every version
+    let (last, content) = bytes.split_last().ok_or(ChallengeError::Malformed)?;
+    if content.is_empty() || checksum(content) != *last {
+        return Err(ChallengeError::Checksum);
+    }
+    if bytes[0] != VERSION {
+        return Err(ChallengeError::UnsupportedVersion(bytes[0]));
+    }
+    if bytes.len() != LENGTH {
+        return Err(ChallengeError::Malformed);
+    }
+    let index = u32::from_be_bytes([bytes[2], bytes[3], bytes[4], bytes[5]]) as usize;
+    let puzzle = match bytes[1] {
+        0 => Puzzle::Answer(index),
+        1 => Puzzle::Daily(index),
+        _ => return Err(ChallengeError::Malformed),
+    };
+    Ok(ChallengeSpec {
+        puzzle,
+        length: bytes[6] as usize,
+        guesses: Some(bytes[7] as usize).filter(|guesses| *guesses > 0),
+    })
+}
+
+/// Code of a finished game, None if the answer isn't one of the possible answers
+/// or the daily puzzle is from a season's schedule.
+pub fn encode_challenge(game_state: &GameState) -> Option<String> {
+    // the code has no room for the season, the friend would get another word
+    if game_state.daily_season.is_some() {
+        return None;
+    }
+    let puzzle = match game_state.daily {
+        Some(daily) => Puzzle::Daily(daily.index),
+        None => Puzzle::Answer(
+            game_state
+                .answers
+                .iter()
+                .position(|w| *w == game_state.word)?,
+        ),
+    };
+    Some(encode(&ChallengeSpec {
+        puzzle,
+        length: game_state.word.chars().count(),
+        guesses: Some(game_state.turns()).filter(|_| game_state.won()),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for spec in [
+            ChallengeSpec {
+                puzzle: Puzzle::Answer(1234),
+                length: 5,
+                guesses: Some(3),
+            },
+            ChallengeSpec {
+                puzzle: Puzzle::Daily(400),
+                length: 5,
+                guesses: None,
+            },
+        ] {
+            let code = encode(&spec);
+            assert!(code
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+            assert_eq!(decode_challenge(&code), Ok(spec));
+        }
+    }
+
+    #[test]
+    fn test_encode_game() {
+        let words = vec!["jolly".to_string(), "hello".to_string()];
+        let mut game_state = GameState::new("hello".to_string(), words.clone(), false);
+        game_state.guesses = vec!["jolly".to_string(), "hello".to_string()];
+        let code = encode_challenge(&game_state).unwrap();
+        assert_eq!(
+            decode_challenge(&code),
+            Ok(ChallengeSpec {
+                puzzle: Puzzle::Answer(1),
+                length: 5,
+                guesses: Some(2),
+            })
+        );
+        game_state.answers = vec!["jolly".to_string()];
+        assert_eq!(encode_challenge(&game_state), None);
+    }
+
+    #[test]
+    fn test_malformed_codes() {
+        let code = encode(&ChallengeSpec {
+            puzzle: Puzzle::Answer(7),
+            length: 5,
+            guesses: Some(4),
+        });
+        assert_eq!(decode_challenge(""), Err(ChallengeError::Malformed));
+        assert_eq!(decode_challenge("garbage"), Err(ChallengeError::Checksum));
+        assert_eq!(
+            decode_challenge("not a code!"),
+            Err(ChallengeError::Malformed)
+        );
+        let mut short = vec![VERSION, 0, 7];
+        short.push(checksum(&short));
+        assert_eq!(
+            decode_challenge(&encode_base64(&short)),
+            Err(ChallengeError::Malformed)
+        );
+        let mut corrupted = code.clone().into_bytes();
+        corrupted[8] = if corrupted[8] == b'A' { b'B' } else { b'A' };
+        assert_eq!(
+            decode_challenge(std::str::from_utf8(&corrupted).unwrap()),
+            Err(ChallengeError::Checksum)
+        );
+        let mut future = vec![2, 0, 0, 0, 0, 7, 5, 4];
+        future.push(checksum(&future));
+        let future = encode_base64(&future);
+        assert_eq!(
+            decode_challenge(&future),
+            Err(ChallengeError::UnsupportedVersion(2))
+        );
+    }
+}
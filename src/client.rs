@@ -0,0 +1,300 @@
+use crate::error::RustleError;
+use crate::protocol::{ClientMessage, ServerMessage};
+use crate::{game_loop, render_game_state, GameState};
+use std::collections::BTreeMap;
+use std::io::{self, stdout, BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::Duration;
+use termion::event::Key;
+use termion::input::TermRead;
+use termion::raw::IntoRawMode;
+
+enum Event {
+    Server(ServerMessage),
+    Disconnected,
+}
+
+/// Local view of a race hosted by a server.
+pub struct Client {
+    name: String,
+    game_state: Option<GameState>,
+    opponents: BTreeMap<String, usize>,
+    solved_by: Option<String>,
+    answer: Option<String>,
+}
+
+impl Client {
+    pub fn new(name: String) -> Client {
+        Client {
+            name,
+            game_state: None,
+            opponents: BTreeMap::new(),
+            solved_by: None,
+            answer: None,
+        }
+    }
+
+    /// Updates the local state with a message received from the server.
+    pub fn handle(&mut self, message: ServerMessage) {
+        match message {
+            ServerMessage::Welcome(length, max_tries) => {
+                self.game_state = Some(GameState::remote(length, max_tries));
+            }
+            ServerMessage::Result(guess, hits) => {
+                if let Some(game_state) = &mut self.game_state {
+                    game_state.add_remote_guess(guess, hits);
+                    game_state.notice = None;
+                }
+            }
+            ServerMessage::Error(message) => {
+                if let Some(game_state) = &mut self.game_state {
+                    game_state.notice = Some(message);
+                }
+            }
+            ServerMessage::Progress(name, guesses) => {
+                if name != self.name {
+                    self.opponents.insert(name, guesses);
+                }
+            }
+            ServerMessage::Solved(name) => self.solved_by = Some(name),
+            ServerMessage::Over(word) => self.answer = Some(word),
+        }
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.game_state
+            .as_ref()
+            .is_some_and(|game_state| game_state.is_over())
+    }
+}
+
+fn render_opponents<W: Write>(stdout: &mut W, client: &Client, game_state: &GameState) {
+    let m_left = 10 + game_state.word.chars().count() as u16 * 2 + 6;
+    let m_top = 3;
+    write!(stdout, "{}Opponents", termion::cursor::Goto(m_left, m_top)).unwrap();
+    for (i, (name, guesses)) in client.opponents.iter().enumerate() {
+        write!(
+            stdout,
+            "{}{}: {}/{}",
+            termion::cursor::Goto(m_left, m_top + 1 + i as u16),
+            name,
+            guesses,
+            game_state.max_tries
+        )
+        .unwrap();
+    }
+    if let Some(name) = &client.solved_by {
+        write!(
+            stdout,
+            "{}Solved by {}",
+            termion::cursor::Goto(m_left, m_top + 2 + client.opponents.len() as u16),
+            name
+        )
+        .unwrap();
+    }
+    stdout.flush().unwrap();
+}
+
+fn render<W: Write>(stdout: &mut W, client: &Client) {
+    match &client.game_state {
+        Some(game_state) => {
+            let mut frame = Vec::new();
+            render_game_state(&mut frame, game_state);
+            render_opponents(&mut frame, client, game_state);
+            stdout.write_all(&frame).unwrap();
+            stdout.flush().unwrap();
+        }
+        None => {
+            write!(
+                stdout,
+                "{}{}Waiting for server...",
+                termion::clear::All,
+                termion::cursor::Goto(10, 3)
+            )
+            .unwrap();
+            stdout.flush().unwrap();
+        }
+    }
+}
+
+/// Connects to the server and forwards its messages as events.
+fn connect(address: &str, name: &str) -> io::Result<(TcpStream, Receiver<Event>)> {
+    let mut stream = TcpStream::connect(address)?;
+    writeln!(stream, "{}", ClientMessage::Hello(name.to_string()))?;
+    let reader = BufReader::new(stream.try_clone()?);
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for line in reader.lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => break,
+            };
+            if let Ok(message) = ServerMessage::parse(&line) {
+                if sender.send(Event::Server(message)).is_err() {
+                    return;
+                }
+            }
+        }
+        let _ = sender.send(Event::Disconnected);
+    });
+    Ok((stream, receiver))
+}
+
+fn play_solo(mut game_state: GameState, notice: String) -> Result<(), RustleError> {
+    game_state.notice = Some(notice);
+    game_loop(game_state).map(|_| ())
+}
+
+/// Plays a race on the server at `address`, falls back to `fallback` if the connection is lost.
+pub fn play(address: &str, name: String, fallback: GameState) -> Result<(), RustleError> {
+    let (mut stream, events) = match connect(address, &name) {
+        Ok(connection) => connection,
+        Err(error) => {
+            return play_solo(
+                fallback,
+                format!("Could not connect to {}: {}, playing solo", address, error),
+            );
+        }
+    };
+
+    let mut client = Client::new(name);
+    let mut stdin = termion::async_stdin().keys();
+    let mut stdout = stdout().into_raw_mode().map_err(RustleError::Terminal)?;
+    let mut dirty = true;
+    loop {
+        loop {
+            match events.try_recv() {
+                Ok(Event::Server(message)) => {
+                    client.handle(message);
+                    dirty = true;
+                }
+                Ok(Event::Disconnected) | Err(TryRecvError::Disconnected) => {
+                    drop(stdout);
+                    return play_solo(fallback, "Connection lost, playing solo".to_string());
+                }
+                Err(TryRecvError::Empty) => break,
+            }
+        }
+        if client.is_over() {
+            break;
+        }
+        if dirty {
+            render(&mut stdout, &client);
+            dirty = false;
+        }
+
+        let key = match stdin.next() {
+            Some(Ok(key)) => key,
+            _ => {
+                thread::sleep(Duration::from_millis(20));
+                continue;
+            }
+        };
+        let game_state = match &mut client.game_state {
+            Some(game_state) => game_state,
+            None if key == Key::Esc => break,
+            None => continue,
+        };
+        match key {
+            Key::Esc => break,
+            Key::Backspace => game_state.back(),
+            Key::Char('\n') => {
+                let guess = std::mem::take(&mut game_state.current_guess);
+                if writeln!(stream, "{}", ClientMessage::Guess(guess)).is_err() {
+                    drop(stdout);
+                    return play_solo(fallback, "Connection lost, playing solo".to_string());
+                }
+            }
+            Key::Char(c) => {
+                game_state.add_char(c);
+            }
+            _ => (),
+        }
+        dirty = true;
+    }
+
+    // the answer is sent right after the last result of a lost game
+    let lost = client.is_over() && !client.game_state.as_ref().is_some_and(|g| g.won());
+    if lost && client.answer.is_none() {
+        while let Ok(Event::Server(message)) = events.recv_timeout(Duration::from_millis(500)) {
+            let over = matches!(message, ServerMessage::Over(_));
+            client.handle(message);
+            if over {
+                break;
+            }
+        }
+    }
+    render(&mut stdout, &client);
+    writeln!(stdout, "{}", termion::cursor::Show).unwrap();
+    drop(stdout);
+
+    let won = client.game_state.as_ref().is_some_and(|g| g.won());
+    if won {
+        println!("You won!");
+    } else if let Some(word) = &client.answer {
+        println!("You lost! The word was: {}", word);
+    }
+    if let Some(name) = &client.solved_by {
+        println!("{} solved it first", name);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HitInfo;
+
+    fn canned(client: &mut Client, lines: &[&str]) {
+        for line in lines {
+            client.handle(ServerMessage::parse(line).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_handle_welcome_and_results() {
+        let mut client = Client::new("niko".to_string());
+        canned(&mut client, &["WELCOME 5 6", "RESULT jolly .YGG."]);
+        let game_state = client.game_state.as_ref().unwrap();
+        assert_eq!(game_state.word.chars().count(), 5);
+        assert_eq!(game_state.guesses, vec!["jolly".to_string()]);
+        assert_eq!(game_state.get_guess_hits(0)[2], HitInfo::Hit);
+        assert!(!client.is_over());
+
+        canned(&mut client, &["RESULT hello GGGGG"]);
+        assert!(client.is_over());
+    }
+
+    #[test]
+    fn test_handle_opponent_progress() {
+        let mut client = Client::new("niko".to_string());
+        canned(
+            &mut client,
+            &[
+                "WELCOME 5 6",
+                "PROGRESS anna 1",
+                "PROGRESS niko 1",
+                "PROGRESS anna 2",
+                "SOLVED anna",
+            ],
+        );
+        assert_eq!(client.opponents.len(), 1);
+        assert_eq!(client.opponents["anna"], 2);
+        assert_eq!(client.solved_by, Some("anna".to_string()));
+    }
+
+    #[test]
+    fn test_handle_error_and_over() {
+        let mut client = Client::new("niko".to_string());
+        canned(&mut client, &["WELCOME 5 6", "ERROR Word is not valid"]);
+        assert_eq!(
+            client.game_state.as_ref().unwrap().notice,
+            Some("Word is not valid".to_string())
+        );
+        canned(&mut client, &["RESULT jolly .YGG.", "OVER hello"]);
+        assert_eq!(client.game_state.as_ref().unwrap().notice, None);
+        assert_eq!(client.answer, Some("hello".to_string()));
+    }
+}
@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Preferences stored in the config file, command line options take precedence.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// "auto", "dark", "light", "colorblind" or "no-color"
+    pub theme: String,
+    /// preferred answer length, used if the word list has words of this length
+    pub word_length: usize,
+    pub hard_mode: bool,
+    /// explain the keyboard colors below the keyboard
+    pub keyboard_legend: bool,
+    /// list the letters known to be in the word below the board
+    pub available_letters: bool,
+    /// list the letters known to be absent below the board
+    pub eliminated_letters: bool,
+    /// show the tries as pips, filled for every used one
+    pub tries_pips: bool,
+    /// rows of the on-screen keyboard, QWERTY plus the other letters of the word list if empty
+    pub keyboard_layout: Vec<String>,
+    /// summarize the word list and modes above the board before the first guess
+    pub header: bool,
+    /// Enter does nothing until the row is full instead of showing an error
+    pub ignore_partial_enter: bool,
+    /// keep the input after a guess which isn't in the word list, so it can be edited
+    pub keep_invalid_input: bool,
+    /// suggest the closest valid word when a guess isn't in the word list
+    pub suggest_words: bool,
+    /// seconds without input after a game before the screensaver starts, 0 turns it off
+    pub screensaver_seconds: u64,
+    /// losses in a row after which an encouraging message is shown, 0 turns it off
+    pub cold_streak: usize,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            theme: "auto".to_string(),
+            word_length: 5,
+            hard_mode: false,
+            keyboard_legend: true,
+            available_letters: false,
+            eliminated_letters: false,
+            tries_pips: false,
+            keyboard_layout: Vec::new(),
+            header: true,
+            ignore_partial_enter: false,
+            keep_invalid_input: false,
+            suggest_words: false,
+            screensaver_seconds: 0,
+            cold_streak: 3,
+        }
+    }
+}
+
+impl Config {
+    pub fn load(path: &Path) -> io::Result<Config> {
+        let content = fs::read_to_string(path)?;
+        serde_json::from_str(&content)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        fs::write(path, content)
+    }
+}
+
+/// `$XDG_CONFIG_HOME/rustle/config.json` or `~/.config/rustle/config.json`.
+pub fn config_path() -> Option<PathBuf> {
+    let dir = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+    Some(dir.join("rustle").join("config.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let config: Config = serde_json::from_str(r#"{"hard_mode": true}"#).unwrap();
+        assert!(config.hard_mode);
+        assert_eq!(config.theme, "auto");
+        assert_eq!(config.word_length, 5);
+    }
+}
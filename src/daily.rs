@@ -0,0 +1,243 @@
+use rand::prelude::*;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Date of the first daily puzzle.
+const FIRST_DAY: Date = Date {
+    year: 2021,
+    month: 6,
+    day: 19,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateError {
+    InvalidFormat(String),
+    InFuture,
+    BeforeFirstDay,
+}
+
+impl std::fmt::Display for DateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            DateError::InvalidFormat(date) => {
+                write!(f, "Invalid date \"{}\", expected YYYY-MM-DD", date)
+            }
+            DateError::InFuture => write!(f, "Date is in the future"),
+            DateError::BeforeFirstDay => write!(f, "There was no daily puzzle on this date"),
+        }
+    }
+}
+
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl Date {
+    /// Parses a date in the format YYYY-MM-DD.
+    pub fn parse(date: &str) -> Result<Date, DateError> {
+        let invalid = || DateError::InvalidFormat(date.to_string());
+        let parts: Vec<&str> = date.split('-').collect();
+        if parts.len() != 3 {
+            return Err(invalid());
+        }
+        let year = parts[0].parse::<i64>().map_err(|_| invalid())?;
+        let month = parts[1].parse::<u32>().map_err(|_| invalid())?;
+        let day = parts[2].parse::<u32>().map_err(|_| invalid())?;
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return Err(invalid());
+        }
+        Ok(Date { year, month, day })
+    }
+
+    /// Days since 1970-01-01.
+    pub fn days_since_epoch(&self) -> i64 {
+        // see http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+        let year = if self.month <= 2 {
+            self.year - 1
+        } else {
+            self.year
+        };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = self.month as i64;
+        let day_of_year =
+            (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146097 + day_of_era - 719468
+    }
+
+    pub fn from_days_since_epoch(days: i64) -> Date {
+        // see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let days = days + 719468;
+        let era = days.div_euclid(146097);
+        let day_of_era = days - era * 146097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        Date { year, month, day }
+    }
+
+    /// Current date in UTC.
+    pub fn today() -> Date {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+        Date::from_days_since_epoch(seconds as i64 / 86400)
+    }
+}
+
+impl std::fmt::Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Number of the daily puzzle of `date`, the first puzzle is number 0.
+pub fn daily_index(date: Date) -> usize {
+    (date.days_since_epoch() - FIRST_DAY.days_since_epoch()).max(0) as usize
+}
+
+/// Date of the daily puzzle number `index`.
+pub fn daily_date(index: usize) -> Date {
+    Date::from_days_since_epoch(FIRST_DAY.days_since_epoch() + index as i64)
+}
+
+/// Answer of the daily puzzle number `index` in a rotating schedule, each
+/// `season` shuffles the `len` answers differently. The shuffled list is used
+/// in order, so no answer repeats before all were used, then a new shuffle starts.
+pub fn scheduled_index(index: usize, len: usize, season: u64) -> usize {
+    let (cycle, position) = (index / len, index % len);
+    let mut order: Vec<usize> = (0..len).collect();
+    let mut rng = StdRng::seed_from_u64(season ^ (cycle as u64).rotate_left(32));
+    order.shuffle(&mut rng);
+    order[position]
+}
+
+/// The daily puzzle being played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DailyGame {
+    pub date: Date,
+    pub index: usize,
+    /// puzzles of past days don't count towards the daily streak
+    pub archive: bool,
+}
+
+impl DailyGame {
+    pub fn for_date(date: Date, today: Date) -> Result<DailyGame, DateError> {
+        if date > today {
+            return Err(DateError::InFuture);
+        }
+        if date < FIRST_DAY {
+            return Err(DateError::BeforeFirstDay);
+        }
+        Ok(DailyGame {
+            date,
+            index: daily_index(date),
+            archive: date != today,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(date: &str) -> Date {
+        Date::parse(date).unwrap()
+    }
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(
+            date("2022-01-31"),
+            Date {
+                year: 2022,
+                month: 1,
+                day: 31
+            }
+        );
+        assert!(Date::parse("2022-02-30").is_err());
+        assert!(Date::parse("2022-13-01").is_err());
+        assert!(Date::parse("yesterday").is_err());
+        assert!(Date::parse("2024-02-29").is_ok());
+    }
+
+    #[test]
+    fn test_days_since_epoch() {
+        assert_eq!(date("1970-01-01").days_since_epoch(), 0);
+        assert_eq!(date("2000-03-01").days_since_epoch(), 11017);
+        for days in [0, 11017, 18797, 20000] {
+            assert_eq!(Date::from_days_since_epoch(days).days_since_epoch(), days);
+        }
+    }
+
+    #[test]
+    fn test_daily_index() {
+        assert_eq!(daily_index(date("2021-06-19")), 0);
+        assert_eq!(daily_index(date("2022-01-01")), 196);
+        assert_eq!(daily_date(196), date("2022-01-01"));
+    }
+
+    #[test]
+    fn test_schedule_uses_every_answer_once() {
+        let len = 50;
+        for cycle in 0..3 {
+            let mut used: Vec<usize> = (cycle * len..(cycle + 1) * len)
+                .map(|index| scheduled_index(index, len, 7))
+                .collect();
+            used.sort_unstable();
+            assert_eq!(used, (0..len).collect::<Vec<usize>>());
+        }
+        let schedule = |season| -> Vec<usize> {
+            (0..len)
+                .map(|index| scheduled_index(index, len, season))
+                .collect()
+        };
+        assert_eq!(schedule(7), schedule(7));
+        assert_ne!(schedule(7), schedule(8));
+        assert_ne!(schedule(7), (0..len).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn test_archive_date_is_stable_and_not_for_streak() {
+        let today = date("2022-02-01");
+        let first = DailyGame::for_date(date("2022-01-10"), today).unwrap();
+        let second = DailyGame::for_date(date("2022-01-10"), today).unwrap();
+        assert_eq!(first.index, second.index);
+        assert!(first.archive);
+        assert!(!DailyGame::for_date(today, today).unwrap().archive);
+    }
+
+    #[test]
+    fn test_rejects_future_dates() {
+        let today = date("2022-02-01");
+        assert_eq!(
+            DailyGame::for_date(date("2022-02-02"), today),
+            Err(DateError::InFuture)
+        );
+        assert_eq!(
+            DailyGame::for_date(date("2020-01-01"), today),
+            Err(DateError::BeforeFirstDay)
+        );
+    }
+}
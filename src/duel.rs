@@ -0,0 +1,218 @@
+use crate::error::RustleError;
+use crate::{game_loop, GameState};
+use std::io::{stdin, stdout, Write};
+use termion::event::Key;
+use termion::input::TermRead;
+use termion::raw::IntoRawMode;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Winner(usize),
+    Draw,
+}
+
+/// Compares the guesses both players needed, `None` if they didn't solve the word.
+pub fn compare(first: Option<usize>, second: Option<usize>) -> Outcome {
+    match (first, second) {
+        (Some(a), Some(b)) if a < b => Outcome::Winner(0),
+        (Some(a), Some(b)) if b < a => Outcome::Winner(1),
+        (Some(_), None) => Outcome::Winner(0),
+        (None, Some(_)) => Outcome::Winner(1),
+        _ => Outcome::Draw,
+    }
+}
+
+pub struct Scoreboard {
+    pub wins: [u32; 2],
+    pub draws: u32,
+}
+
+impl Scoreboard {
+    pub fn new() -> Scoreboard {
+        Scoreboard {
+            wins: [0, 0],
+            draws: 0,
+        }
+    }
+
+    pub fn record(&mut self, outcome: Outcome) {
+        match outcome {
+            Outcome::Winner(player) => self.wins[player] += 1,
+            Outcome::Draw => self.draws += 1,
+        }
+    }
+}
+
+const PLAYERS: [&str; 2] = ["Player 1", "Player 2"];
+
+/// Shows `message` on a cleared screen and waits for Enter, returns false on Esc.
+fn prompt(message: &str) -> bool {
+    let mut stdout = stdout().into_raw_mode().unwrap();
+    write!(
+        stdout,
+        "{}{}{}",
+        termion::clear::All,
+        termion::cursor::Goto(10, 3),
+        message
+    )
+    .unwrap();
+    stdout.flush().unwrap();
+    for key in stdin().keys() {
+        match key.unwrap() {
+            Key::Char('\n') => return true,
+            Key::Esc => return false,
+            _ => (),
+        }
+    }
+    false
+}
+
+/// Lets the setter type the secret word without showing it.
+fn enter_secret(setter: &str, template: &GameState) -> Option<String> {
+    let length = template.word.chars().count();
+    let mut stdout = stdout().into_raw_mode().unwrap();
+    let mut secret = String::new();
+    let mut error: Option<&str> = None;
+    let mut keys = stdin().keys();
+    loop {
+        write!(
+            stdout,
+            "{}{}{}, enter a secret word with {} letters: {}{}{}",
+            termion::clear::All,
+            termion::cursor::Goto(10, 3),
+            setter,
+            length,
+            "*".repeat(secret.chars().count()),
+            termion::cursor::Goto(10, 5),
+            error.unwrap_or("")
+        )
+        .unwrap();
+        stdout.flush().unwrap();
+        match keys.next()?.unwrap() {
+            Key::Esc => return None,
+            Key::Backspace => {
+                secret.pop();
+            }
+            Key::Char('\n') => {
+                if secret.chars().count() != length {
+                    error = Some("Word is not the correct length");
+                } else if !template.any_word && !template.valid_words.contains(&secret) {
+                    error = Some("Word is not valid");
+                } else {
+                    return Some(secret);
+                }
+                secret.clear();
+            }
+            Key::Char(c) if secret.chars().count() < length => {
+                secret.push(c.to_lowercase().next().unwrap());
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Plays a single turn, returns the guesses needed or `None` if the word wasn't solved.
+/// The outer `None` means a player quit.
+fn play_turn(setter: usize, template: &GameState) -> Result<Option<Option<usize>>, RustleError> {
+    let guesser = 1 - setter;
+    let secret = match enter_secret(PLAYERS[setter], template) {
+        Some(secret) => secret,
+        None => return Ok(None),
+    };
+    if !prompt(&format!(
+        "Pass the keyboard to {} and press Enter",
+        PLAYERS[guesser]
+    )) {
+        return Ok(None);
+    }
+    let mut game_state = template.clone();
+    game_state.word = secret;
+    let game_state = game_loop(game_state)?;
+    let result = if game_state.won() {
+        format!(
+            "{} solved \"{}\" in {} guesses",
+            PLAYERS[guesser],
+            game_state.word,
+            game_state.turns()
+        )
+    } else {
+        format!("{} did not solve \"{}\"", PLAYERS[guesser], game_state.word)
+    };
+    prompt(&format!("{}. Press Enter to continue", result));
+    Ok(Some(game_state.won().then_some(game_state.turns())))
+}
+
+/// Plays rounds of pass-and-play until a player quits with Esc.
+pub fn play(template: GameState) -> Result<(), RustleError> {
+    let mut scoreboard = Scoreboard::new();
+    // player 1 guesses the word of player 2 and vice versa
+    while let Some(first) = play_turn(1, &template)? {
+        let second = match play_turn(0, &template)? {
+            Some(result) => result,
+            None => break,
+        };
+        let outcome = compare(first, second);
+        scoreboard.record(outcome);
+
+        let result = match outcome {
+            Outcome::Winner(player) => format!("{} wins the round!", PLAYERS[player]),
+            Outcome::Draw => "The round is a draw!".to_string(),
+        };
+        let message = format!(
+            "{} Score: {} {} - {} {} ({} draws). Enter for another round, Esc to quit",
+            result,
+            PLAYERS[0],
+            scoreboard.wins[0],
+            scoreboard.wins[1],
+            PLAYERS[1],
+            scoreboard.draws
+        );
+        if !prompt(&message) {
+            break;
+        }
+    }
+    let mut stdout = stdout();
+    writeln!(
+        stdout,
+        "{}{}Final score: {} {} - {} {} ({} draws)",
+        termion::clear::All,
+        termion::cursor::Goto(1, 1),
+        PLAYERS[0],
+        scoreboard.wins[0],
+        scoreboard.wins[1],
+        PLAYERS[1],
+        scoreboard.draws
+    )
+    .unwrap();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_fewer_guesses_wins() {
+        assert_eq!(compare(Some(3), Some(4)), Outcome::Winner(0));
+        assert_eq!(compare(Some(5), Some(2)), Outcome::Winner(1));
+        assert_eq!(compare(Some(3), Some(3)), Outcome::Draw);
+    }
+
+    #[test]
+    fn test_compare_unsolved() {
+        assert_eq!(compare(Some(6), None), Outcome::Winner(0));
+        assert_eq!(compare(None, Some(6)), Outcome::Winner(1));
+        assert_eq!(compare(None, None), Outcome::Draw);
+    }
+
+    #[test]
+    fn test_scoreboard_records_rounds() {
+        let mut scoreboard = Scoreboard::new();
+        scoreboard.record(Outcome::Winner(0));
+        scoreboard.record(Outcome::Winner(1));
+        scoreboard.record(Outcome::Winner(1));
+        scoreboard.record(Outcome::Draw);
+        assert_eq!(scoreboard.wins, [1, 2]);
+        assert_eq!(scoreboard.draws, 1);
+    }
+}
@@ -0,0 +1,62 @@
+//! Errors which end the program, `main` prints them and exits.
+
+use std::io;
+
+#[derive(Debug)]
+pub enum RustleError {
+    /// a list of words couldn't be read, `list` names which one
+    WordList {
+        list: &'static str,
+        error: io::Error,
+    },
+    Io(io::Error),
+    /// the terminal couldn't be switched to raw mode or read from
+    Terminal(io::Error),
+    /// an invalid option value, challenge code or answer
+    Usage(String),
+    /// a validated word list has this many problems, the report lists them
+    InvalidWordFile(usize),
+}
+
+impl RustleError {
+    /// Exit code of the program, 2 for usage errors like clap uses.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            RustleError::Usage(_) => 2,
+            _ => 1,
+        }
+    }
+}
+
+impl std::fmt::Display for RustleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RustleError::WordList { list, error } => {
+                write!(f, "Could not load {}: {}", list, error)
+            }
+            RustleError::Io(error) => write!(f, "{}", error),
+            RustleError::Terminal(error) => write!(f, "Terminal error: {}", error),
+            RustleError::Usage(message) => write!(f, "{}", message),
+            RustleError::InvalidWordFile(issues) => {
+                write!(f, "The word list has {} problem(s)", issues)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RustleError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RustleError::WordList { error, .. }
+            | RustleError::Io(error)
+            | RustleError::Terminal(error) => Some(error),
+            RustleError::Usage(_) | RustleError::InvalidWordFile(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for RustleError {
+    fn from(error: io::Error) -> RustleError {
+        RustleError::Io(error)
+    }
+}
@@ -0,0 +1,66 @@
+use crate::HitInfo;
+
+/// How much of the feedback of a guess is shown to the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedbackStrategy {
+    /// letters are marked as correct, present or absent
+    Standard,
+    /// letters are only marked as in the word or absent, correct letters look like present ones
+    Binary,
+    /// standard feedback for the first guess, binary feedback for all later ones
+    BinaryAfterOpener,
+}
+
+impl FeedbackStrategy {
+    /// Feedback shown for `hits` of the guess made in `turn`, counted from 0.
+    pub fn apply(&self, turn: usize, hits: Vec<HitInfo>) -> Vec<HitInfo> {
+        match self {
+            FeedbackStrategy::Standard => hits,
+            FeedbackStrategy::BinaryAfterOpener if turn == 0 => hits,
+            FeedbackStrategy::Binary | FeedbackStrategy::BinaryAfterOpener => hits
+                .into_iter()
+                .map(|hit| match hit {
+                    HitInfo::Hit => HitInfo::Contains,
+                    hit => hit,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameState;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_standard_keeps_feedback() {
+        let hits = GameState::compute_hits("hello", "jolly");
+        assert_eq!(FeedbackStrategy::Standard.apply(3, hits.clone()), hits);
+    }
+
+    #[test]
+    fn test_binary_has_two_states() {
+        let mut states = HashSet::new();
+        for guess in ["hello", "jolly", "world", "fuzzy", "lohel"] {
+            let hits = GameState::compute_hits("hello", guess);
+            states.extend(FeedbackStrategy::Binary.apply(0, hits));
+        }
+        assert_eq!(
+            states,
+            [HitInfo::Contains, HitInfo::Miss].into_iter().collect()
+        );
+    }
+
+    #[test]
+    fn test_only_the_opener_gets_full_feedback() {
+        let hits = GameState::compute_hits("hello", "jolly");
+        let strategy = FeedbackStrategy::BinaryAfterOpener;
+        assert_eq!(strategy.apply(0, hits.clone()), hits);
+        let later = strategy.apply(1, hits.clone());
+        assert_ne!(later, hits);
+        assert!(!later.contains(&HitInfo::Hit));
+        assert_eq!(later, FeedbackStrategy::Binary.apply(1, hits));
+    }
+}
@@ -0,0 +1,322 @@
+//! Log of every finished game, one JSON object per line, for analysing the
+//! games with other tools. Logs ending in `.bin` store the entries in the
+//! more compact bincode format instead, one after another.
+
+use crate::protocol::encode_hits;
+use crate::GameState;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// seconds since the Unix epoch
+    pub timestamp: u64,
+    pub modes: Vec<String>,
+    /// left out in private entries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answer: Option<String>,
+    /// left out in private entries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guesses: Option<Vec<String>>,
+    /// feedback of every guess as it was shown, G for a hit, Y for a letter in the wrong
+    /// place and . for a miss
+    pub patterns: Vec<String>,
+    /// milliseconds taken for every turn of a timed game, empty otherwise
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub guess_times: Vec<u64>,
+    pub won: bool,
+    /// unused guesses plus one on a win minus the mulligans used and hints, 0 on a loss
+    pub score: u32,
+}
+
+impl HistoryEntry {
+    /// Entry of a finished game, `private` entries only keep the feedback
+    /// and neither the answer nor the guessed words.
+    pub fn new(game_state: &GameState, timestamp: u64, private: bool) -> HistoryEntry {
+        let won = game_state.won();
+        let score = if won {
+            let unused = (game_state.max_tries as usize + 1).saturating_sub(game_state.turns());
+            (unused as u32)
+                .saturating_sub(game_state.mulligans_used)
+                .saturating_sub(game_state.hints.len() as u32)
+        } else {
+            0
+        };
+        HistoryEntry {
+            timestamp,
+            modes: game_state.modes(),
+            answer: Some(game_state.word.clone()).filter(|_| !private),
+            guesses: Some(game_state.guesses.clone()).filter(|_| !private),
+            patterns: (0..game_state.guesses.len())
+                .map(|i| encode_hits(&game_state.visible_hits(i)))
+                .collect(),
+            guess_times: game_state
+                .guess_times
+                .iter()
+                .map(|time| time.as_millis() as u64)
+                .collect(),
+            won,
+            score,
+        }
+    }
+}
+
+/// `HistoryEntry` as it is stored in bincode, which can't skip fields,
+/// so private entries keep their empty answer and guesses.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct BinaryEntry {
+    timestamp: u64,
+    modes: Vec<String>,
+    answer: Option<String>,
+    guesses: Option<Vec<String>>,
+    patterns: Vec<String>,
+    guess_times: Vec<u64>,
+    won: bool,
+    score: u32,
+}
+
+impl From<HistoryEntry> for BinaryEntry {
+    fn from(entry: HistoryEntry) -> BinaryEntry {
+        BinaryEntry {
+            timestamp: entry.timestamp,
+            modes: entry.modes,
+            answer: entry.answer,
+            guesses: entry.guesses,
+            patterns: entry.patterns,
+            guess_times: entry.guess_times,
+            won: entry.won,
+            score: entry.score,
+        }
+    }
+}
+
+impl From<BinaryEntry> for HistoryEntry {
+    fn from(entry: BinaryEntry) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: entry.timestamp,
+            modes: entry.modes,
+            answer: entry.answer,
+            guesses: entry.guesses,
+            patterns: entry.patterns,
+            guess_times: entry.guess_times,
+            won: entry.won,
+            score: entry.score,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// one JSON object per line
+    Json,
+    /// bincode entries without separators
+    Binary,
+}
+
+impl LogFormat {
+    /// Binary for paths ending in `.bin`, JSON otherwise.
+    pub fn for_path(path: &Path) -> LogFormat {
+        if path.extension().is_some_and(|ext| ext == "bin") {
+            LogFormat::Binary
+        } else {
+            LogFormat::Json
+        }
+    }
+
+    /// Bytes of `entry` as they are appended to a log.
+    pub fn encode(self, entry: &HistoryEntry) -> io::Result<Vec<u8>> {
+        match self {
+            LogFormat::Json => {
+                let mut line = serde_json::to_vec(entry)
+                    .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+                line.push(b'\n');
+                Ok(line)
+            }
+            LogFormat::Binary => bincode::serialize(&BinaryEntry::from(entry.clone()))
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error)),
+        }
+    }
+}
+
+/// Path the log is moved to once it gets too large, replacing an older one.
+fn rotated_path(path: &Path) -> PathBuf {
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(".old");
+    PathBuf::from(rotated)
+}
+
+/// Appends `entry` in the format of the path. If the log is already larger
+/// than `max_bytes`, it is moved aside first so a fresh log is started.
+pub fn append(path: &Path, entry: &HistoryEntry, max_bytes: Option<u64>) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    if let (Some(max_bytes), Ok(metadata)) = (max_bytes, fs::metadata(path)) {
+        if metadata.len() > max_bytes {
+            fs::rename(path, rotated_path(path))?;
+        }
+    }
+    let bytes = LogFormat::for_path(path).encode(entry)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(&bytes)
+}
+
+/// Appends the finished game with the current time.
+pub fn record(
+    path: &Path,
+    game_state: &GameState,
+    private: bool,
+    max_bytes: Option<u64>,
+) -> io::Result<()> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs());
+    append(
+        path,
+        &HistoryEntry::new(game_state, timestamp, private),
+        max_bytes,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finished_game() -> GameState {
+        let words = vec!["hello".to_string(), "jolly".to_string()];
+        let mut game_state = GameState::new("hello".to_string(), words, false);
+        game_state.guesses = vec!["jolly".to_string(), "hello".to_string()];
+        game_state
+    }
+
+    #[test]
+    fn test_finished_game_appends_one_line() {
+        let path =
+            std::env::temp_dir().join(format!("rustle-history-{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let game_state = finished_game();
+        record(&path, &game_state, false, None).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content.lines().count(), 1);
+        let entry: HistoryEntry = serde_json::from_str(content.lines().next().unwrap()).unwrap();
+        assert_eq!(entry.answer.as_deref(), Some("hello"));
+        assert_eq!(
+            entry.guesses,
+            Some(vec!["jolly".to_string(), "hello".to_string()])
+        );
+        assert_eq!(entry.patterns, vec![".YGG.", "GGGGG"]);
+        assert!(entry.won);
+        assert_eq!(entry.score, 5);
+        assert_eq!(entry.modes, vec!["standard"]);
+
+        record(&path, &game_state, false, None).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(content.lines().count(), 2);
+    }
+
+    #[test]
+    fn test_mulligans_and_hints_lower_the_score() {
+        let mut game_state = finished_game();
+        game_state.mulligans_used = 1;
+        assert_eq!(HistoryEntry::new(&game_state, 0, false).score, 4);
+        game_state.hints = vec![0];
+        assert_eq!(HistoryEntry::new(&game_state, 0, false).score, 3);
+    }
+
+    #[test]
+    fn test_private_entry_keeps_only_patterns() {
+        let entry = HistoryEntry::new(&finished_game(), 0, true);
+        let line = serde_json::to_string(&entry).unwrap();
+        assert!(!line.contains("hello"));
+        assert!(!line.contains("jolly"));
+        assert!(line.contains(r#""patterns":[".YGG.","GGGGG"]"#));
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert!(value.get("answer").is_none());
+        assert!(value.get("guesses").is_none());
+        let entry: HistoryEntry = serde_json::from_str(&line).unwrap();
+        assert_eq!(entry.answer, None);
+        assert_eq!(entry.guesses, None);
+        assert!(entry.won);
+    }
+
+    #[test]
+    fn test_private_jsonl_entry_has_no_answer_key() {
+        let path = std::env::temp_dir().join(format!(
+            "rustle-history-private-{}.jsonl",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        record(&path, &finished_game(), true, None).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert!(!content.contains(r#""answer""#));
+        assert!(!content.contains(r#""guesses""#));
+    }
+
+    #[test]
+    fn test_binary_log_matches_json() {
+        let path =
+            std::env::temp_dir().join(format!("rustle-history-binary-{}.bin", std::process::id()));
+        let _ = fs::remove_file(&path);
+        assert_eq!(LogFormat::for_path(&path), LogFormat::Binary);
+        let mut timed = finished_game();
+        timed.guess_times = vec![std::time::Duration::from_secs(2); 2];
+        let entries = [
+            HistoryEntry::new(&timed, 1, false),
+            HistoryEntry::new(&finished_game(), 2, true),
+        ];
+        for entry in &entries {
+            append(&path, entry, None).unwrap();
+        }
+        let bytes = fs::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        let mut reader = bytes.as_slice();
+        for entry in &entries {
+            let json = LogFormat::Json.encode(entry).unwrap();
+            let from_json: HistoryEntry = serde_json::from_slice(&json).unwrap();
+            let from_binary: BinaryEntry = bincode::deserialize_from(&mut reader).unwrap();
+            assert_eq!(HistoryEntry::from(from_binary), from_json);
+        }
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn test_entry_keeps_shown_feedback_and_times() {
+        let mut game_state = finished_game();
+        game_state.feedback = crate::feedback::FeedbackStrategy::Binary;
+        game_state.guess_times = vec![
+            std::time::Duration::from_millis(1500),
+            std::time::Duration::from_secs(3),
+        ];
+        let entry = HistoryEntry::new(&game_state, 0, false);
+        assert_eq!(entry.patterns, vec![".YYY.", "YYYYY"]);
+        assert_eq!(entry.guess_times, vec![1500, 3000]);
+        let line = serde_json::to_string(&entry).unwrap();
+        assert!(line.contains(r#""guess_times":[1500,3000]"#));
+
+        let untimed =
+            serde_json::to_string(&HistoryEntry::new(&finished_game(), 0, false)).unwrap();
+        assert!(!untimed.contains("guess_times"));
+    }
+
+    #[test]
+    fn test_large_log_is_rotated() {
+        let path = std::env::temp_dir().join(format!(
+            "rustle-history-rotate-{}.jsonl",
+            std::process::id()
+        ));
+        let entry = HistoryEntry::new(&finished_game(), 0, false);
+        fs::write(&path, "x".repeat(100)).unwrap();
+        append(&path, &entry, Some(50)).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        let rotated = fs::read_to_string(rotated_path(&path)).unwrap();
+        fs::remove_file(&path).unwrap();
+        fs::remove_file(rotated_path(&path)).unwrap();
+        assert_eq!(content.lines().count(), 1);
+        assert_eq!(rotated.len(), 100);
+    }
+}
@@ -0,0 +1,144 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+const QWERTY: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+/// Layout of the on-screen keyboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keyboard {
+    pub rows: Vec<Vec<char>>,
+}
+
+impl Keyboard {
+    pub fn qwerty() -> Keyboard {
+        Keyboard {
+            rows: QWERTY.iter().map(|row| row.chars().collect()).collect(),
+        }
+    }
+
+    /// Keyboard with the given rows, e.g. `["qwertzuiopü", "asdfghjklöä", "yxcvbnm"]`.
+    pub fn from_rows(rows: &[String]) -> Keyboard {
+        Keyboard {
+            rows: rows
+                .iter()
+                .map(|row| {
+                    row.chars()
+                        .flat_map(char::to_lowercase)
+                        .filter(|c| !c.is_whitespace())
+                        .collect::<Vec<char>>()
+                })
+                .filter(|row| !row.is_empty())
+                .collect(),
+        }
+    }
+
+    /// QWERTY with an extra row for the letters of `words` it lacks, like umlauts.
+    pub fn for_words(words: &[String]) -> Keyboard {
+        let mut keyboard = Keyboard::qwerty();
+        keyboard.add_letters(words);
+        keyboard
+    }
+
+    /// Adds a row for the letters of `words` the keyboard lacks.
+    /// Returns whether there were any.
+    pub fn add_letters(&mut self, words: &[String]) -> bool {
+        let known = self.letters();
+        let mut extra: Vec<char> = words
+            .iter()
+            .flat_map(|word| word.chars())
+            .filter(|c| !known.contains(c))
+            .collect();
+        extra.sort_unstable();
+        extra.dedup();
+        if extra.is_empty() {
+            return false;
+        }
+        self.rows.push(extra);
+        true
+    }
+
+    /// Places the letters in random order, keeping the row lengths.
+    pub fn shuffled<R: Rng>(&self, rng: &mut R) -> Keyboard {
+        let mut letters = self.letters();
+        letters.shuffle(rng);
+        let mut letters = letters.into_iter();
+        Keyboard {
+            rows: self
+                .rows
+                .iter()
+                .map(|row| letters.by_ref().take(row.len()).collect())
+                .collect(),
+        }
+    }
+
+    pub fn letters(&self) -> Vec<char> {
+        self.rows.iter().flatten().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_qwerty_layout() {
+        let keyboard = Keyboard::qwerty();
+        assert_eq!(keyboard.rows.len(), 3);
+        assert_eq!(keyboard.letters().len(), 26);
+        assert_eq!(keyboard.rows[1][0], 'a');
+    }
+
+    #[test]
+    fn test_shuffled_has_same_letters() {
+        let keyboard = Keyboard::qwerty().shuffled(&mut StdRng::seed_from_u64(42));
+        let mut letters = keyboard.letters();
+        let mut qwerty = Keyboard::qwerty().letters();
+        assert_ne!(letters, qwerty);
+        letters.sort_unstable();
+        qwerty.sort_unstable();
+        assert_eq!(letters, qwerty);
+        let lengths: Vec<usize> = keyboard.rows.iter().map(|row| row.len()).collect();
+        assert_eq!(lengths, vec![10, 9, 7]);
+    }
+
+    #[test]
+    fn test_layout_from_rows() {
+        let rows = ["qwertzuiopü", "asdfghjklöä", "", "y x c v b n m"].map(String::from);
+        let keyboard = Keyboard::from_rows(&rows);
+        assert_eq!(keyboard.rows.len(), 3);
+        assert_eq!(keyboard.rows[0].last(), Some(&'ü'));
+        assert_eq!(keyboard.rows[2], "yxcvbnm".chars().collect::<Vec<char>>());
+    }
+
+    #[test]
+    fn test_layout_for_words_adds_missing_letters() {
+        let words = ["hallo", "größe", "übel"].map(String::from);
+        let keyboard = Keyboard::for_words(&words);
+        assert_eq!(keyboard.rows.len(), 4);
+        assert_eq!(keyboard.rows[3], vec!['ß', 'ö', 'ü']);
+        assert_eq!(
+            Keyboard::for_words(&["hello".to_string()]),
+            Keyboard::qwerty()
+        );
+    }
+
+    #[test]
+    fn test_add_letters_keeps_the_layout() {
+        let mut keyboard = Keyboard::qwerty().shuffled(&mut StdRng::seed_from_u64(3));
+        let shuffled = keyboard.clone();
+        assert!(!keyboard.add_letters(&["hello".to_string()]));
+        assert_eq!(keyboard, shuffled);
+        assert!(keyboard.add_letters(&["hällö".to_string()]));
+        assert_eq!(keyboard.rows[..3], shuffled.rows[..]);
+        assert_eq!(keyboard.rows[3], vec!['ä', 'ö']);
+    }
+
+    #[test]
+    fn test_shuffle_is_seeded() {
+        let first = Keyboard::qwerty().shuffled(&mut StdRng::seed_from_u64(7));
+        let second = Keyboard::qwerty().shuffled(&mut StdRng::seed_from_u64(7));
+        assert_eq!(first, second);
+    }
+}
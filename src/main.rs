@@ -1,16 +1,54 @@
 extern crate termion;
 
-use clap::{App, Arg};
+mod achievements;
+mod benchmark;
+mod border;
+mod broadcast;
+mod challenge;
+mod client;
+mod config;
+mod daily;
+mod duel;
+mod error;
+mod feedback;
+mod history;
+mod keyboard;
+mod protocol;
+mod screensaver;
+mod server;
+mod session;
+mod share;
+mod solver;
+mod stats;
+mod theme;
+mod timer;
+mod watch;
+mod wizard;
+mod words;
+
+use achievements::Achievements;
+use border::BorderStyle;
+use challenge::{ChallengeSpec, Puzzle};
+use clap::{App, Arg, ArgGroup, ArgMatches};
+use config::Config;
+use daily::{daily_date, DailyGame, Date};
+use error::RustleError;
+use feedback::FeedbackStrategy;
+use keyboard::Keyboard;
 use rand::prelude::*;
-use std::fs::File;
-use std::io::Read;
-use std::io::{stdin, stdout, Write};
-use termion::color;
+use session::Session;
+use stats::Stats;
+use std::collections::HashMap;
+use std::io;
+use std::io::{stdin, stdout, BufRead, Write};
+use std::time::{Duration, Instant};
 use termion::event::Key;
 use termion::input::TermRead;
 use termion::raw::IntoRawMode;
+use theme::Theme;
+use timer::Timer;
 
-#[derive(Debug, Eq, PartialEq, Clone)]
+#[derive(Debug, Eq, PartialEq, Clone, Hash, PartialOrd, Ord)]
 enum HitInfo {
     Hit,
     Contains,
@@ -18,10 +56,31 @@ enum HitInfo {
     None,
 }
 
+/// Letter filling the row of a turn forfeited in timed mode or for invalid guesses.
+const FORFEIT: char = '-';
+
+/// Largest edit distance of a suggested word to a rejected guess.
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// Range of tries which can be picked with +/- before a new game.
+const MIN_TRIES: u16 = 1;
+const MAX_TRIES: u16 = 12;
+
+/// Openers banned by `--ban-openers` unless a list is given with `--banned-openers`.
+const BANNED_OPENERS: [&str; 10] = [
+    "crane", "slate", "salet", "trace", "crate", "arise", "raise", "adieu", "roate", "soare",
+];
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 enum GameError {
     WrongLength,
     InvalidWord,
+    MissingHint,
+    BannedOpener,
+    RepeatedLetters,
+    AnswerAsOpener,
+    NotAnagram,
+    GameOver,
 }
 
 impl std::fmt::Display for GameError {
@@ -29,517 +88,5203 @@ impl std::fmt::Display for GameError {
         match self {
             GameError::WrongLength => write!(f, "Word is not the correct length"),
             GameError::InvalidWord => write!(f, "Word is not valid"),
+            GameError::MissingHint => write!(f, "Word must use all revealed hints"),
+            GameError::BannedOpener => write!(f, "This opener is banned, try another word"),
+            GameError::RepeatedLetters => write!(f, "Word must not repeat a letter"),
+            GameError::AnswerAsOpener => write!(f, "The answer can't be the first guess"),
+            GameError::NotAnagram => write!(f, "Word must rearrange the letters of the last guess"),
+            GameError::GameOver => write!(f, "The game is over"),
+        }
+    }
+}
+
+/// How the answer is revealed after a lost game.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum RevealDelay {
+    Immediate,
+    Pause(Duration),
+    /// wait for a key press
+    Key,
+}
+
+impl RevealDelay {
+    /// Parses a delay in milliseconds or "key".
+    fn parse(value: &str) -> Option<RevealDelay> {
+        match value {
+            "key" => Some(RevealDelay::Key),
+            _ => match value.parse::<u64>().ok()? {
+                0 => Some(RevealDelay::Immediate),
+                ms => Some(RevealDelay::Pause(Duration::from_millis(ms))),
+            },
         }
     }
 }
 
+#[derive(Clone)]
 struct GameState {
     valid_words: Vec<String>,
+    answers: Vec<String>,
     guesses: Vec<String>,
+    // board rows of the turns lost to the turn limit or invalid guesses
+    forfeits: Vec<usize>,
     current_guess: String,
     word: String,
     max_tries: u16,
     last_error: Option<GameError>,
+    // closest valid word to a guess rejected as invalid
+    suggestion: Option<String>,
+    suggest_words: bool,
     any_word: bool,
+    // guesses of another length are compared with the answer where both have letters
+    overlap: bool,
+    prefix_check: bool,
+    // the guess is made as soon as the row is full
+    auto_submit: bool,
+    // guesses have to use all revealed hints
+    hard_mode: bool,
+    // no limit on the number of guesses
+    zen: bool,
+    // feedback is only shown once all guesses are made
+    concealed: bool,
+    // ring the terminal bell on invalid guesses and wins
+    bell: bool,
+    // the word is played again after a loss
+    retry: bool,
+    // frequency of the answer in annotated word lists
+    frequency: Option<f64>,
+    show_difficulty: bool,
+    show_vowels: bool,
+    show_remaining: bool,
+    remaining_reveals: u32,
+    invalid_attempts: u32,
+    // every this many invalid attempts cost a turn
+    invalid_penalty: Option<u32>,
+    // letters shown as absent on request, in the order they were revealed
+    revealed_absent: Vec<char>,
+    notice: Option<String>,
+    // feedback received from a server, the word is unknown locally
+    remote_hits: Option<Vec<Vec<HitInfo>>>,
+    // number of players taking turns on the board
+    players: usize,
+    daily: Option<DailyGame>,
+    // season of the rotating daily schedule, challenge codes can't carry it
+    daily_season: Option<u64>,
+    // seed which picked the answer of a random game
+    seed: Option<u64>,
+    theme: Theme,
+    border: BorderStyle,
+    compact: bool,
+    keyboard: Option<Keyboard>,
+    keyboard_legend: bool,
+    // list the letters known to be in the word
+    available_letters: bool,
+    // list the letters known to be absent
+    eliminated_letters: bool,
+    // show used and remaining tries as pips
+    tries_pips: bool,
+    // print how many candidates each guess eliminated after an assisted game
+    explain: bool,
+    // print the guesses the solver would have played after the game
+    optimal_line: bool,
+    // print the letters tried at every position after the game
+    position_letters: bool,
+    // show how good the full input would be, in assist mode
+    quality: bool,
+    assist: bool,
+    show_preview: bool,
+    // file receiving a text copy of the board after every render
+    broadcast: Option<std::path::PathBuf>,
+    timer: Option<Timer>,
+    // time taken for every guess in timed games
+    guess_times: Vec<Duration>,
+    // the theme was switched while playing and should be saved
+    theme_changed: bool,
+    // emphasize the keys the last guess told something new about
+    highlight_new: bool,
+    // statuses learned with the last guess, until the board is drawn once
+    new_statuses: HashMap<char, HitInfo>,
+    // how much of the feedback is shown
+    feedback: FeedbackStrategy,
+    // Enter does nothing until the row is full instead of showing an error
+    ignore_partial_enter: bool,
+    // a word which isn't in the word list stays in the input for editing
+    keep_invalid_input: bool,
+    // suspense before the answer of a lost game is shown
+    reveal_delay: RevealDelay,
+    // started from a challenge code
+    challenge: Option<ChallengeSpec>,
+    // words which may not be used as the first guess
+    banned_openers: Vec<String>,
+    // guesses may not use a letter twice
+    unique_letters: bool,
+    // every guess after the first rearranges the letters of the one before
+    anagram: bool,
+    // the known answer of a practice game can't be the first guess
+    forbid_answer_opener: bool,
+    // reveal a correct letter before the last try
+    auto_hint: bool,
+    // positions revealed by hints
+    hints: Vec<usize>,
+    // guesses which may be taken back per game
+    max_mulligans: u32,
+    mulligans_used: u32,
+    // Ctrl+D prints the answer to stderr, only with RUSTLE_DEBUG=1 for test automation
+    debug: bool,
+    // name of the word list, "English" for the embedded one
+    word_list: String,
+    // word files the word list was loaded from, reloaded with Ctrl+R
+    word_files: Vec<String>,
+    word_sources: words::CompositePolicy,
+    word_filters: WordFilters,
+    // summarize the word list and modes above the board until the first guess
+    show_header: bool,
+    // show the attempt number left of every row
+    numbered: bool,
+    // reveal the tiles of a confirmed guess one after another
+    animate: bool,
+    // reveal the tiles in a random order instead of left to right
+    random_reveal: bool,
+    // the reveal order of every guess is derived from this seed
+    reveal_seed: u64,
+    // tiles of the last guess revealed so far while animating
+    revealing: Option<Vec<usize>>,
 }
 
 impl GameState {
     pub fn new(word: String, valid_words: Vec<String>, any_word: bool) -> GameState {
         GameState {
+            answers: valid_words.clone(),
             valid_words,
             guesses: Vec::new(),
+            forfeits: Vec::new(),
             current_guess: String::new(),
             word,
             max_tries: 6,
             last_error: None,
+            suggestion: None,
+            suggest_words: false,
             any_word,
+            overlap: false,
+            prefix_check: false,
+            auto_submit: false,
+            hard_mode: false,
+            zen: false,
+            concealed: false,
+            bell: false,
+            retry: false,
+            frequency: None,
+            show_difficulty: false,
+            show_vowels: false,
+            show_remaining: false,
+            remaining_reveals: 0,
+            invalid_attempts: 0,
+            invalid_penalty: None,
+            revealed_absent: Vec::new(),
+            notice: None,
+            remote_hits: None,
+            players: 1,
+            daily: None,
+            daily_season: None,
+            seed: None,
+            theme: Theme::dark(),
+            border: BorderStyle::ascii(),
+            compact: false,
+            keyboard: Some(Keyboard::qwerty()),
+            keyboard_legend: false,
+            available_letters: false,
+            eliminated_letters: false,
+            tries_pips: false,
+            explain: false,
+            optimal_line: false,
+            position_letters: false,
+            quality: false,
+            assist: false,
+            show_preview: false,
+            broadcast: None,
+            timer: None,
+            guess_times: Vec::new(),
+            theme_changed: false,
+            highlight_new: false,
+            new_statuses: HashMap::new(),
+            feedback: FeedbackStrategy::Standard,
+            ignore_partial_enter: false,
+            keep_invalid_input: false,
+            reveal_delay: RevealDelay::Immediate,
+            challenge: None,
+            banned_openers: Vec::new(),
+            unique_letters: false,
+            anagram: false,
+            forbid_answer_opener: false,
+            auto_hint: false,
+            hints: Vec::new(),
+            max_mulligans: 0,
+            mulligans_used: 0,
+            debug: false,
+            word_list: "English".to_string(),
+            word_files: Vec::new(),
+            word_sources: words::CompositePolicy::Fallback,
+            word_filters: WordFilters::default(),
+            show_header: false,
+            numbered: false,
+            animate: false,
+            random_reveal: false,
+            reveal_seed: rand::random(),
+            revealing: None,
+        }
+    }
+
+    /// Creates a game whose guesses are validated by a server.
+    pub fn remote(length: usize, max_tries: u16) -> GameState {
+        let mut game_state = GameState::new("_".repeat(length), Vec::new(), true);
+        game_state.max_tries = max_tries;
+        game_state.remote_hits = Some(Vec::new());
+        game_state
+    }
+
+    pub fn add_remote_guess(&mut self, guess: String, hits: Vec<HitInfo>) {
+        if let Some(remote_hits) = &mut self.remote_hits {
+            self.guesses.push(guess);
+            remote_hits.push(hits);
         }
     }
 
     fn guess(&mut self, guess: String) -> Result<bool, GameError> {
-        if guess.chars().count() != self.word.chars().count() {
+        if self.is_over() {
+            return Err(GameError::GameOver);
+        }
+        let length = guess.chars().count();
+        if length != self.word.chars().count() && !(self.overlap && length > 0) {
             return Err(GameError::WrongLength);
         }
         if !self.any_word && !self.valid_words.contains(&guess) {
             return Err(GameError::InvalidWord);
         }
+        if self.guesses.is_empty() && self.banned_openers.contains(&guess) {
+            return Err(GameError::BannedOpener);
+        }
+        if self.forbid_answer_opener && self.guesses.is_empty() && guess == self.word {
+            return Err(GameError::AnswerAsOpener);
+        }
+        if self.unique_letters && words::has_repeated_letters(&guess) {
+            return Err(GameError::RepeatedLetters);
+        }
+        let last = self.guesses.last();
+        if self.anagram && last.is_some_and(|last| !words::is_anagram(last, &guess)) {
+            return Err(GameError::NotAnagram);
+        }
+        // hints are unknown while they are concealed
+        if self.hard_mode && !self.feedback_hidden() && !self.uses_hints(&guess) {
+            return Err(GameError::MissingHint);
+        }
         self.guesses.push(guess);
         Ok(self.won())
     }
 
+    /// Replaces the answer, it has to be an accepted word of the same length.
+    pub fn set_word(&mut self, word: &str) -> Result<(), GameError> {
+        let word = word.trim().to_lowercase();
+        if word.chars().count() != self.word.chars().count() {
+            return Err(GameError::WrongLength);
+        }
+        if !self.valid_words.contains(&word) {
+            return Err(GameError::InvalidWord);
+        }
+        self.word = word;
+        self.frequency = None;
+        // the answer no longer comes from the seed
+        self.seed = None;
+        Ok(())
+    }
+
+    /// Checks that `guess` keeps every hit in place and contains every letter found so far.
+    pub fn uses_hints(&self, guess: &str) -> bool {
+        let letters: Vec<char> = guess.chars().collect();
+        (0..self.guesses.len()).all(|i| {
+            self.guesses[i]
+                .chars()
+                .zip(self.visible_hits(i))
+                .enumerate()
+                .all(|(j, (c, hit))| match hit {
+                    HitInfo::Hit => letters.get(j) == Some(&c),
+                    HitInfo::Contains => letters.contains(&c),
+                    _ => true,
+                })
+        })
+    }
+
     fn set_last_error(&mut self, error: GameError) {
         self.last_error = Some(error);
     }
 
     fn reset_error(&mut self) {
         self.last_error = None;
+        self.suggestion = None;
+    }
+
+    /// The last error, with the suggested word if there is one.
+    pub fn error_message(&self) -> Option<String> {
+        let error = self.last_error?;
+        Some(match &self.suggestion {
+            Some(suggestion) => format!("{}, did you mean \"{}\"?", error, suggestion),
+            None => error.to_string(),
+        })
+    }
+
+    /// Valid word of the same length closest to `guess`, if it is only a typo away.
+    pub fn nearest_valid(&self, guess: &str) -> Option<String> {
+        let length = guess.chars().count();
+        let mut nearest: Option<(usize, &String)> = None;
+        for word in self
+            .valid_words
+            .iter()
+            .filter(|w| w.chars().count() == length)
+        {
+            let max = nearest.map_or(MAX_SUGGESTION_DISTANCE, |(distance, _)| distance - 1);
+            match words::edit_distance(guess, word, max) {
+                Some(0) => continue,
+                Some(distance) => nearest = Some((distance, word)),
+                None => (),
+            }
+            if nearest.is_some_and(|(distance, _)| distance == 1) {
+                break;
+            }
+        }
+        nearest.map(|(_, word)| word.clone())
     }
 
     pub fn won(&self) -> bool {
+        if self.concealed {
+            return self.guesses.contains(&self.word);
+        }
+        if let Some(remote_hits) = &self.remote_hits {
+            return remote_hits
+                .last()
+                .is_some_and(|hits| hits.iter().all(|hit| hit == &HitInfo::Hit));
+        }
         match self.guesses.last() {
             Some(last_guess) => last_guess == &self.word,
             None => false,
         }
     }
 
-    pub fn get_guess_hits(&self, guess_position: usize) -> Vec<HitInfo> {
-        let mut hits = Vec::new();
-        let guess = self.guesses.get(guess_position).unwrap();
-        for (i, c) in guess.chars().enumerate() {
-            if c == self.word.chars().nth(i).unwrap() {
-                hits.push(HitInfo::Hit);
-            } else if self.word.contains(c) {
-                hits.push(HitInfo::Contains);
-            } else {
-                hits.push(HitInfo::Miss);
-            }
+    /// Only today's daily puzzle counts towards the daily streak.
+    pub fn counts_toward_streak(&self) -> bool {
+        self.daily.is_some_and(|daily| !daily.archive)
+    }
+
+    pub fn is_over(&self) -> bool {
+        if self.concealed {
+            return self.turns() >= self.max_tries as usize;
         }
-        hits
+        self.won() || (!self.zen && self.turns() >= self.max_tries as usize)
     }
 
-    pub fn back(&mut self) {
-        if self.current_guess.chars().count() > 0 {
-            self.current_guess.pop();
+    /// Turns used so far, the guesses and the forfeited turns.
+    pub fn turns(&self) -> usize {
+        self.guesses.len() + self.forfeits.len()
+    }
+
+    /// Guess shown in board row `row`, None for a forfeited turn or a row not played yet.
+    pub fn guess_in_row(&self, row: usize) -> Option<usize> {
+        if row >= self.turns() || self.forfeits.contains(&row) {
+            return None;
         }
+        Some(row - self.forfeits.iter().filter(|f| **f < row).count())
     }
 
-    pub fn confirm(&mut self) {
-        let result = self.guess(self.current_guess.clone());
-        match result {
-            Ok(_) => {
-                self.reset_error();
-            }
-            Err(error) => {
-                self.set_last_error(error);
-            }
-        };
-        self.current_guess = String::new();
+    /// Board row of guess `i`, forfeited turns before it take up rows as well.
+    pub fn row_of_guess(&self, i: usize) -> usize {
+        (0..self.turns())
+            .find(|row| self.guess_in_row(*row) == Some(i))
+            .unwrap_or(i)
+    }
+
+    /// In concealed mode no feedback is shown before the game is over.
+    pub fn feedback_hidden(&self) -> bool {
+        self.concealed && !self.is_over()
     }
 
-    pub fn add_char(&mut self, c: char) {
-        if self.current_guess.chars().count() < self.word.chars().count() {
-            self.current_guess.push(c.to_lowercase().next().unwrap());
+    /// Feedback of a guess as shown on the board.
+    pub fn shown_hits(&self, guess_position: usize) -> Vec<HitInfo> {
+        if self.feedback_hidden() {
+            return vec![HitInfo::None; self.guesses[guess_position].chars().count()];
+        }
+        let hits = self.visible_hits(guess_position);
+        match &self.revealing {
+            Some(revealed) if guess_position + 1 == self.guesses.len() => hits
+                .into_iter()
+                .enumerate()
+                .map(|(i, hit)| {
+                    if revealed.contains(&i) {
+                        hit
+                    } else {
+                        HitInfo::None
+                    }
+                })
+                .collect(),
+            _ => hits,
         }
     }
-}
 
-fn render_game_state(game_state: &GameState) {
-    let mut stdout = stdout().into_raw_mode().unwrap();
-    writeln!(stdout, "{}{}", termion::clear::All, termion::cursor::Hide).unwrap();
-    let width = game_state.word.chars().count() as u16;
-    let height = game_state.max_tries as u16;
-    let m_top = 4;
-    let m_left = 10;
-    for y in 0..height {
-        write!(
-            stdout,
-            "{}{}",
-            termion::cursor::Goto(m_left, m_top + y * 2 - 1),
-            (0..(width * 2 + 1)).map(|_| "-").collect::<String>()
-        )
-        .unwrap();
+    /// Attempt (starting at 1) in which every position was first correct, None if it never was.
+    pub fn first_hits(&self) -> Vec<Option<usize>> {
+        (0..self.word.chars().count())
+            .map(|x| {
+                (0..self.guesses.len())
+                    .find(|i| self.get_guess_hits(*i).get(x) == Some(&HitInfo::Hit))
+                    .map(|i| self.row_of_guess(i) + 1)
+            })
+            .collect()
+    }
 
-        // get guess of line or a string of underscores
-        let line_guess: String;
-        if y < game_state.guesses.len() as u16 {
-            line_guess = game_state.guesses[y as usize].clone();
-        } else if y == game_state.guesses.len() as u16 {
-            let mut curr_guess = game_state.current_guess.clone();
-            while curr_guess.chars().count() < width as usize {
-                curr_guess.push('_');
+    /// Guess with the most hits, then the most contained letters. Ties go to the earliest guess,
+    /// None if no guess found a single letter.
+    pub fn closest_guess(&self) -> Option<usize> {
+        let score = |i: usize| {
+            let hits = self.get_guess_hits(i);
+            let count = |info: HitInfo| hits.iter().filter(|hit| **hit == info).count();
+            (count(HitInfo::Hit), count(HitInfo::Contains))
+        };
+        // max_by_key keeps the last maximum, so search backwards
+        (0..self.guesses.len())
+            .rev()
+            .max_by_key(|i| score(*i))
+            .filter(|i| score(*i) != (0, 0))
+    }
+
+    /// Letters the guesses placed at every position of the answer, sorted.
+    /// Letters past the end of the answer are left out.
+    pub fn attempted_letters(&self) -> Vec<Vec<char>> {
+        let mut positions = vec![Vec::new(); self.word.chars().count()];
+        for guess in &self.guesses {
+            for (letters, c) in positions.iter_mut().zip(guess.chars()) {
+                if !letters.contains(&c) {
+                    letters.push(c);
+                }
             }
-            line_guess = curr_guess;
-        } else {
-            line_guess = (0..width).map(|_| "_").collect::<String>();
         }
+        for letters in &mut positions {
+            letters.sort_unstable();
+        }
+        positions
+    }
 
-        // get hits of line
-        let line_hits: Vec<HitInfo>;
-        if (y as usize) < game_state.guesses.len() {
-            line_hits = game_state.get_guess_hits(y as usize);
+    pub fn next_theme(&mut self) {
+        self.theme = self.theme.next();
+        self.theme_changed = true;
+    }
+
+    /// Delay before the answer is shown, only lost games which weren't quit are delayed.
+    pub fn reveal_gate(&self) -> RevealDelay {
+        if self.is_over() && !self.won() {
+            self.reveal_delay
         } else {
-            line_hits = vec![HitInfo::None; width as usize];
+            RevealDelay::Immediate
         }
+    }
 
-        for x in 0..width {
-            // print each letter into a cell
-            write!(
-                stdout,
-                "{}|",
-                termion::cursor::Goto(m_left + x * 2, m_top + y * 2),
-            )
-            .unwrap();
+    /// Short identifier of the puzzle, the number of the daily puzzle or the seed of
+    /// a random game. None if the answer was chosen otherwise.
+    pub fn game_id(&self) -> Option<String> {
+        match (&self.daily, self.seed) {
+            (Some(daily), _) => Some(format!("daily #{}", daily.index)),
+            (None, Some(seed)) => Some(format!("seed {}", seed)),
+            (None, None) => None,
+        }
+    }
 
-            // set color according to hit info
-            let hit_info = line_hits.get(x as usize).unwrap();
-            if hit_info == &HitInfo::Hit {
-                write!(
-                    stdout,
-                    "{}{}",
-                    color::Bg(color::Green),
-                    color::Fg(color::Black),
-                )
-                .unwrap();
-            }
-            if hit_info == &HitInfo::Contains {
-                write!(
-                    stdout,
-                    "{}{}",
-                    color::Bg(color::Yellow),
-                    color::Fg(color::Black),
-                )
-                .unwrap();
-            }
-            if hit_info == &HitInfo::Miss {
-                write!(
-                    stdout,
-                    "{}{}",
-                    color::Bg(color::Black),
-                    color::Fg(color::White),
-                )
-                .unwrap();
-            }
-            if hit_info == &HitInfo::None {
-                write!(
-                    stdout,
-                    "{}{}",
-                    color::Bg(color::Reset),
-                    color::Fg(color::Reset)
-                )
-                .unwrap();
+    /// Active modes, "standard" if there are none.
+    pub fn modes(&self) -> Vec<String> {
+        let mut modes = Vec::new();
+        if let Some(daily) = &self.daily {
+            modes.push(format!("daily #{}", daily.index));
+        }
+        for (active, mode) in [
+            (self.hard_mode, "hard"),
+            (self.zen, "zen"),
+            (self.concealed, "concealed"),
+            (self.feedback == FeedbackStrategy::Binary, "binary"),
+            (
+                self.feedback == FeedbackStrategy::BinaryAfterOpener,
+                "binary after opener",
+            ),
+            (self.unique_letters, "unique letters"),
+            (self.anagram, "anagram"),
+            (self.timer.is_some(), "timed"),
+            (self.any_word, "any word"),
+            (self.invalid_penalty.is_some(), "invalid penalty"),
+        ] {
+            if active {
+                modes.push(mode.to_string());
             }
+        }
+        if !self.zen && self.max_tries != 6 {
+            modes.push(format!("{} tries", self.max_tries));
+        }
+        if modes.is_empty() {
+            modes.push("standard".to_string());
+        }
+        modes
+    }
 
-            write!(
-                stdout,
-                "{}{}{}",
-                line_guess.chars().nth(x as usize).unwrap(),
-                color::Bg(color::Reset),
-                color::Fg(color::Reset)
-            )
-            .unwrap();
+    /// Word length, number of accepted guesses, word list and active modes.
+    pub fn header(&self) -> String {
+        let length = self.word.chars().count();
+        let guesses = self
+            .valid_words
+            .iter()
+            .filter(|w| w.chars().count() == length)
+            .count();
+        format!(
+            "{} letters, {} valid guesses, {}, {}",
+            length,
+            guesses,
+            self.word_list,
+            self.modes().join(", ")
+        )
+    }
+
+    /// Order in which the tiles of a guess are revealed, seeded per guess.
+    pub fn reveal_order(&self, guess_position: usize) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.guesses[guess_position].chars().count()).collect();
+        if self.random_reveal {
+            let mut rng =
+                StdRng::seed_from_u64(self.reveal_seed.wrapping_add(guess_position as u64));
+            order.shuffle(&mut rng);
         }
-        // close cell
-        writeln!(stdout, "|").unwrap();
+        order
     }
-    // print error below game board
-    match game_state.last_error {
-        Some(error) => {
-            writeln!(
-                stdout,
-                "{}{}",
-                termion::cursor::Goto(m_left, m_top + height * 2 + 1),
-                format!("{}", error)
-            )
-            .unwrap();
+
+    /// Zen games and retries are kept out of the persisted stats.
+    pub fn counts_for_stats(&self) -> bool {
+        !self.zen && !self.retry
+    }
+
+    /// Guesses left before the game is lost, unlimited in zen mode.
+    pub fn remaining_tries(&self) -> usize {
+        if self.zen {
+            return usize::MAX;
         }
-        None => (),
+        (self.max_tries as usize).saturating_sub(self.turns())
     }
-}
 
-fn game_loop(mut game_state: GameState) {
-    let mut stdin = stdin().keys();
-    let mut stdout = stdout().into_raw_mode().unwrap();
-    'game_loop: while game_state.guesses.len() < 6 {
-        render_game_state(&game_state);
-        'input_loop: loop {
-            let b = stdin.next().unwrap().unwrap();
-            match b {
-                Key::Esc => break 'game_loop,
-                Key::Backspace => game_state.back(),
-                Key::Char('\n') => {
-                    game_state.confirm();
-                    break 'input_loop;
-                }
-                Key::Char(c) => game_state.add_char(c),
-                _ => (),
+    /// Reveals the letter of the first position no guess has found yet.
+    pub fn hint(&mut self) -> Option<(usize, char)> {
+        let position = self
+            .first_hits()
+            .iter()
+            .enumerate()
+            .position(|(x, first)| first.is_none() && !self.hints.contains(&x))?;
+        self.hints.push(position);
+        self.word.chars().nth(position).map(|c| (position, c))
+    }
+
+    /// Gives a hint on the last try if auto hints are enabled, once per game.
+    pub fn auto_hint(&mut self) -> bool {
+        if !self.auto_hint || self.remaining_tries() != 1 || !self.hints.is_empty() {
+            return false;
+        }
+        match self.hint() {
+            Some((position, c)) => {
+                self.notice = Some(format!(
+                    "Last try! Letter {} is {}",
+                    position + 1,
+                    c.to_uppercase()
+                ));
+                true
             }
-            stdout.flush().unwrap();
-            render_game_state(&game_state);
+            None => false,
         }
+    }
 
-        match game_state.last_error {
-            None => {
-                if game_state.won() {
-                    println!("You won!");
-                    break;
-                }
-            }
-            _ => (),
+    /// The answer for the debug command, None unless debugging was enabled.
+    pub fn debug_answer(&self) -> Option<&str> {
+        Some(self.word.as_str()).filter(|_| self.debug)
+    }
+
+    /// Takes back the last guess if there are mulligans left, returns whether it did.
+    /// A forfeited turn in the last row is taken back instead.
+    pub fn undo_last_guess(&mut self) -> bool {
+        if self.mulligans_used >= self.max_mulligans || self.remote_hits.is_some() {
+            return false;
+        }
+        let last_row = self.turns().checked_sub(1);
+        if last_row.is_some() && self.forfeits.last() == last_row.as_ref() {
+            self.forfeits.pop();
+        } else if self.guesses.pop().is_none() {
+            return false;
         }
+        if self.guess_times.len() > self.turns() {
+            self.guess_times.pop();
+        }
+        self.mulligans_used += 1;
+        self.reset_error();
+        true
     }
 
-    render_game_state(&game_state);
-    writeln!(stdout, "{}", termion::cursor::Show).unwrap();
-    if !game_state.won() {
-        println!("You lost! The word was: {}", game_state.word);
+    /// Starts over with the same answer, the retry doesn't count towards the stats.
+    pub fn retry_same_word(&mut self) {
+        self.guesses.clear();
+        self.forfeits.clear();
+        self.mulligans_used = 0;
+        self.invalid_attempts = 0;
+        self.hints.clear();
+        self.current_guess = String::new();
+        self.reset_error();
+        self.show_remaining = false;
+        self.revealed_absent.clear();
+        self.guess_times.clear();
+        if let Some(timer) = &mut self.timer {
+            *timer = Timer::new(Instant::now(), timer.turn_limit);
+        }
+        self.retry = true;
     }
-}
 
-fn init_game(any_word: bool, word_file: Option<&str>) -> GameState {
-    // load valid word list from file
-    let mut words = Vec::new();
-    let word;
+    /// Rows shown on the board. Zen games can have more guesses than rows,
+    /// the board then scrolls to keep the input row visible.
+    pub fn visible_rows(&self) -> std::ops::Range<usize> {
+        let height = self.max_tries as usize;
+        let first = if self.zen {
+            (self.turns() + 1).saturating_sub(height)
+        } else {
+            0
+        };
+        first..first + height
+    }
 
-    match word_file {
-        Some(file) => {
-            let mut file = File::open(file).unwrap();
-            let mut contents = String::new();
-            file.read_to_string(&mut contents).unwrap();
-            words = contents
-                .split('\n')
-                .map(|s| s.trim().to_string().to_lowercase())
-                .collect();
+    /// Player whose turn it is, players take turns after every guess.
+    pub fn current_player(&self) -> usize {
+        self.turns() % self.players
+    }
 
-            let mut rng = rand::thread_rng();
-            let i = rng.gen::<usize>() % words.len();
-            word = words[i].clone();
+    /// Player who entered the solving guess.
+    pub fn solving_player(&self) -> Option<usize> {
+        if self.won() {
+            Some((self.turns() - 1) % self.players)
+        } else {
+            None
         }
-        None => {
-            // special list of words acceptable as solutions
-            let picked_word_str = include_str!("../data/picked_words.txt");
-            for line in picked_word_str.lines() {
-                words.push(line.to_string().to_lowercase());
-            }
-
-            let mut rng = rand::thread_rng();
-            let i = rng.gen::<usize>() % words.len();
-            word = words[i].clone();
+    }
 
-            // all other words
-            let valid_word_str = include_str!("../data/valid_words.txt");
-            for line in valid_word_str.lines() {
-                words.push(line.to_string().to_lowercase());
+    /// Best status of every letter guessed so far.
+    pub fn letter_statuses(&self) -> HashMap<char, HitInfo> {
+        let mut statuses: HashMap<char, HitInfo> = HashMap::new();
+        for (i, guess) in self.guesses.iter().enumerate() {
+            for (c, hit) in guess.chars().zip(self.visible_hits(i)) {
+                let status = statuses.entry(c).or_insert(HitInfo::None);
+                // Hit < Contains < Miss < None
+                if hit < *status {
+                    *status = hit;
+                }
             }
         }
+        for c in &self.revealed_absent {
+            statuses.insert(*c, HitInfo::Miss);
+        }
+        statuses
     }
 
-    let game_state = GameState::new(word, words, any_word);
-    game_state
-}
+    /// Letters whose status is new or better in `after` than in `before`.
+    pub fn status_diff(
+        before: &HashMap<char, HitInfo>,
+        after: &HashMap<char, HitInfo>,
+    ) -> HashMap<char, HitInfo> {
+        after
+            .iter()
+            .filter(|(c, status)| before.get(c).map_or(true, |old| *status < old))
+            .map(|(c, status)| (*c, status.clone()))
+            .collect()
+    }
 
-fn main() {
-    let matches = App::new("Rordle")
-        .version("0.2.0")
-        .author("Niko Abeler <niko@rerere.org>")
-        .about("A Wordle clone for the terminal")
-        .arg(
-            Arg::new("any-word")
-                .short('a')
-                .long("any-word")
-                .takes_value(false)
-                .help("Allow any word to be guessed"),
-        )
-        .arg(
-            Arg::new("word-file")
-                .short('w')
-                .long("word-file")
-                .takes_value(true)
-                .help("Use a word list from a file"),
-        )
-        .get_matches();
+    /// Letters known to be in the word, without revealing where they go.
+    pub fn available_letters(&self) -> Vec<char> {
+        let mut letters: Vec<char> = self
+            .letter_statuses()
+            .into_iter()
+            .filter(|(_, status)| matches!(status, HitInfo::Hit | HitInfo::Contains))
+            .map(|(c, _)| c)
+            .collect();
+        letters.sort_unstable();
+        letters
+    }
 
-    let game_state = init_game(
-        matches.is_present("any-word"),
-        matches.value_of("word-file"),
-    );
-    game_loop(game_state)
-}
+    /// Letters known to be absent, a letter found by any guess isn't eliminated.
+    pub fn eliminated_letters(&self) -> Vec<char> {
+        let mut letters: Vec<char> = self
+            .letter_statuses()
+            .into_iter()
+            .filter(|(_, status)| *status == HitInfo::Miss)
+            .map(|(c, _)| c)
+            .collect();
+        letters.sort_unstable();
+        letters
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Feedback of a guess the player gets to know, depending on the feedback strategy.
+    pub fn visible_hits(&self, guess_position: usize) -> Vec<HitInfo> {
+        self.feedback
+            .apply(guess_position, self.get_guess_hits(guess_position))
+    }
 
-    #[test]
-    fn test_new_guess() {
-        let mut game_state =
-            super::GameState::new("hello".to_string(), vec!["hello".to_string()], false);
-        let result = game_state.guess("hello".to_string());
-        assert_eq!(result.unwrap(), true);
-        assert_eq!(game_state.guesses.len(), 1);
-        assert_eq!(game_state.guesses[0], "hello".to_string());
+    pub fn get_guess_hits(&self, guess_position: usize) -> Vec<HitInfo> {
+        if let Some(remote_hits) = &self.remote_hits {
+            return remote_hits[guess_position].clone();
+        }
+        self.hits_for(&self.word, &self.guesses[guess_position])
     }
 
-    #[test]
-    fn test_new_guess_umlaut() {
-        let mut game_state =
-            super::GameState::new("hello".to_string(), vec!["hällö".to_string()], false);
-        let result = game_state.guess("hällö".to_string());
-        assert_eq!(result.unwrap(), false);
-        assert_eq!(game_state.guesses.len(), 1);
-        assert_eq!(game_state.guesses[0], "hällö".to_string());
+    /// Feedback `guess` receives if `answer` is the word, in the mode of this game.
+    fn hits_for(&self, answer: &str, guess: &str) -> Vec<HitInfo> {
+        if self.overlap {
+            GameState::compute_overlap_hits(answer, guess)
+        } else {
+            GameState::compute_hits(answer, guess)
+        }
     }
 
-    #[test]
-    fn test_new_guess_miss() {
-        let mut game_state = super::GameState::new(
-            "hello".to_string(),
-            vec!["hello".to_string(), "world".to_string()],
-            false,
-        );
-        let result = game_state.guess("world".to_string());
-        assert_eq!(result.unwrap(), false);
-        assert_eq!(game_state.guesses.len(), 1);
-        assert_eq!(game_state.guesses[0], "world".to_string());
+    /// Feedback like `compute_hits` for a guess which may be shorter or longer than
+    /// `answer`. Only the positions both words have are compared, a letter there is
+    /// present if it is anywhere in the answer. Letters past the end of the answer
+    /// get no feedback, so they aren't taken as absent.
+    pub fn compute_overlap_hits(answer: &str, guess: &str) -> Vec<HitInfo> {
+        let mut hits = GameState::compute_hits(answer, guess);
+        for hit in hits.iter_mut().skip(answer.chars().count()) {
+            *hit = HitInfo::None;
+        }
+        hits
     }
 
-    #[test]
-    fn test_guess_rejects_word_of_wrong_length() {
-        let mut game_state =
-            super::GameState::new("hello".to_string(), vec!["hello".to_string()], false);
-        let result = game_state.guess("hell".to_string());
-        match result {
-            Err(GameError::WrongLength) => assert!(true),
-            _ => assert!(false, "No error raised for wrong length"),
+    /// Feedback `guess` receives if `answer` is the word.
+    pub fn compute_hits(answer: &str, guess: &str) -> Vec<HitInfo> {
+        guess
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                if answer.chars().nth(i) == Some(c) {
+                    HitInfo::Hit
+                } else if answer.contains(c) {
+                    HitInfo::Contains
+                } else {
+                    HitInfo::Miss
+                }
+            })
+            .collect()
+    }
+
+    /// Checks if `prefix` can still be completed to a valid word of the right length.
+    pub fn has_valid_prefix(&self, prefix: &str) -> bool {
+        if self.any_word {
+            return true;
         }
-        assert_eq!(game_state.guesses.len(), 0);
+        let length = self.word.chars().count();
+        self.valid_words
+            .iter()
+            .any(|w| w.chars().count() == length && w.starts_with(prefix))
     }
 
-    #[test]
-    fn test_guess_rejects_invalid_words() {
-        let mut game_state =
-            super::GameState::new("hello".to_string(), vec!["hello".to_string()], false);
-        let result = game_state.guess("jello".to_string());
-        match result {
-            Err(GameError::InvalidWord) => assert!(true),
-            _ => assert!(false, "No error raised for invalid word"),
+    /// Possible answers which are consistent with all guesses so far.
+    pub fn remaining_candidates(&self) -> Vec<String> {
+        let length = self.word.chars().count();
+        let mut candidates: Vec<String> = self
+            .answers
+            .iter()
+            .filter(|w| w.chars().count() == length)
+            .cloned()
+            .collect();
+        for i in 0..self.guesses.len() {
+            candidates = self.filter_by_guess(candidates, i);
         }
-        assert_eq!(game_state.guesses.len(), 0);
+        candidates
     }
 
-    #[test]
-    fn test_get_guess_hits() {
+    /// Keeps the candidates which would have shown the same feedback for guess `i`.
+    fn filter_by_guess(&self, candidates: Vec<String>, i: usize) -> Vec<String> {
+        let guess = &self.guesses[i];
+        let shown = self.visible_hits(i);
+        let length = self.word.chars().count();
+        candidates
+            .into_iter()
+            .filter(|c| {
+                c.chars().count() == length
+                    && self.feedback.apply(i, self.hits_for(c, guess)) == shown
+            })
+            .collect()
+    }
+
+    /// Candidates left before and after every guess, to judge the guesses after the game.
+    pub fn explanation(&self) -> Vec<GuessExplanation> {
+        let length = self.word.chars().count();
+        let mut candidates: Vec<String> = self
+            .answers
+            .iter()
+            .filter(|w| w.chars().count() == length)
+            .cloned()
+            .collect();
+        let mut rows = Vec::new();
+        for (i, guess) in self.guesses.iter().enumerate() {
+            let before = candidates.len();
+            candidates = self.filter_by_guess(candidates, i);
+            rows.push(GuessExplanation {
+                guess: guess.clone(),
+                before,
+                after: candidates.len(),
+            });
+        }
+        rows
+    }
+
+    /// Letters of the positions shown as correct so far.
+    pub fn known_greens(&self) -> Vec<Option<char>> {
+        let mut known = vec![None; self.word.chars().count()];
+        for (i, guess) in self.guesses.iter().enumerate() {
+            for ((c, hit), green) in guess.chars().zip(self.shown_hits(i)).zip(&mut known) {
+                if hit == HitInfo::Hit {
+                    *green = Some(c);
+                }
+            }
+        }
+        known
+    }
+
+    /// Number of remaining candidates matching a full input with wildcards, in assist mode.
+    pub fn wildcard_count(&self) -> Option<usize> {
+        if !self.assist
+            || !self.current_guess.contains(solver::WILDCARD)
+            || self.current_guess.chars().count() != self.word.chars().count()
+        {
+            return None;
+        }
+        let candidates = self.remaining_candidates();
+        Some(solver::match_wildcards(&candidates, &self.current_guess, &self.known_greens()).len())
+    }
+
+    /// Number of possible answers which are consistent with all guesses so far.
+    pub fn remaining_candidate_count(&self) -> usize {
+        self.remaining_candidates().len()
+    }
+
+    /// Feedback patterns `candidate` could receive and how many remaining candidates each leaves.
+    /// Only the remaining candidates are considered, so the answer is never revealed.
+    pub fn preview(&self, candidate: &str) -> Vec<(Vec<HitInfo>, usize)> {
+        solver::partition(&self.remaining_candidates(), candidate)
+    }
+
+    /// Expected share of the remaining candidates the full input would eliminate.
+    /// Only computed in assist mode once the input has the length of the word.
+    pub fn guess_quality(&self) -> Option<f64> {
+        if !self.quality
+            || !self.assist
+            || self.feedback_hidden()
+            || self.current_guess.chars().count() != self.word.chars().count()
+        {
+            return None;
+        }
+        solver::expected_elimination(&self.remaining_candidates(), &self.current_guess)
+    }
+
+    pub fn toggle_preview(&mut self) {
+        if self.assist && !self.feedback_hidden() {
+            self.show_preview = !self.show_preview;
+        }
+    }
+
+    pub fn reveal_remaining(&mut self) {
+        if !self.show_remaining && !self.feedback_hidden() {
+            self.show_remaining = true;
+            self.remaining_reveals += 1;
+        }
+    }
+
+    pub fn back(&mut self) {
+        if self.current_guess.chars().count() > 0 {
+            self.current_guess.pop();
+            self.reset_error();
+        }
+    }
+
+    pub fn confirm(&mut self) {
+        if self.ignore_partial_enter
+            && self.current_guess.chars().count() < self.word.chars().count()
+        {
+            return;
+        }
+        // a pattern only tests a hypothesis, it isn't a guess
+        if let Some(count) = self.wildcard_count() {
+            self.notice = Some(format!(
+                "{} possible answers match {}",
+                count, self.current_guess
+            ));
+            return;
+        }
+        let before = if self.highlight_new {
+            self.letter_statuses()
+        } else {
+            HashMap::new()
+        };
+        let result = self.guess(self.current_guess.clone());
+        match result {
+            Ok(_) => {
+                if self.highlight_new {
+                    self.new_statuses = GameState::status_diff(&before, &self.letter_statuses());
+                }
+                self.reset_error();
+                self.show_remaining = false;
+                if let Some(timer) = &mut self.timer {
+                    self.guess_times.push(timer.next_turn(Instant::now()));
+                }
+            }
+            Err(error) => {
+                self.invalid_attempts += 1;
+                self.set_last_error(error);
+                if self
+                    .invalid_penalty
+                    .is_some_and(|n| self.invalid_attempts % n == 0)
+                {
+                    self.forfeit_turn(Instant::now());
+                    self.notice = Some(format!(
+                        "{} invalid guesses cost a turn",
+                        self.invalid_attempts
+                    ));
+                }
+                if self.suggest_words && error == GameError::InvalidWord {
+                    self.suggestion = self.nearest_valid(&self.current_guess);
+                }
+                if self.keep_invalid_input && error == GameError::InvalidWord {
+                    return;
+                }
+            }
+        };
+        self.current_guess = String::new();
+    }
+
+    /// Ends the turn with an empty row once its time limit ran out.
+    /// Returns true if the turn was forfeited.
+    pub fn check_turn_timer(&mut self, now: Instant) -> bool {
+        let expired = self.timer.is_some_and(|timer| timer.turn_expired(now));
+        if !expired || self.is_over() {
+            return false;
+        }
+        self.forfeit_turn(now);
+        self.current_guess = String::new();
+        self.reset_error();
+        true
+    }
+
+    /// Uses up the turn with an empty row.
+    fn forfeit_turn(&mut self, now: Instant) {
+        if self.is_over() {
+            return;
+        }
+        if let Some(timer) = &mut self.timer {
+            self.guess_times.push(timer.next_turn(now));
+        }
+        self.forfeits.push(self.turns());
+    }
+
+    /// Marks a random letter as absent which isn't in the answer and wasn't shown as a miss yet.
+    pub fn reveal_absent_letter(&mut self) -> Option<char> {
+        if self.feedback_hidden() {
+            return None;
+        }
+        let statuses = self.letter_statuses();
+        let letters: Vec<char> = match &self.keyboard {
+            Some(keyboard) => keyboard.letters(),
+            None => ('a'..='z').collect(),
+        };
+        let absent: Vec<char> = letters
+            .into_iter()
+            .filter(|c| !self.word.contains(*c) && !statuses.contains_key(c))
+            .collect();
+        let letter = *absent.choose(&mut rand::thread_rng())?;
+        self.revealed_absent.push(letter);
+        Some(letter)
+    }
+
+    /// Reads the word files again and accepts their words for the following guesses,
+    /// filtered like the words of a new game. The answer stays the same and is kept valid.
+    /// Returns the number of words.
+    pub fn reload_words(&mut self) -> io::Result<usize> {
+        if self.word_files.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "the embedded word list can't be reloaded",
+            ));
+        }
+        let list = words::CompositeWordProvider::for_sources(&self.word_files, self.word_sources)
+            .resolve()?;
+        let (mut words, frequencies) = words::split_frequencies(list.words);
+        words.extend(list.extra_guesses);
+        self.valid_words =
+            self.word_filters
+                .accepted_guesses(words, &self.answers, &frequencies, &self.word);
+        // new letters get a key, the keys already there stay put
+        if let Some(keyboard) = &mut self.keyboard {
+            keyboard.add_letters(&self.valid_words);
+        }
+        Ok(self.valid_words.len())
+    }
+
+    /// Pauses or resumes the clock of a timed game.
+    pub fn toggle_pause(&mut self, now: Instant) {
+        if let Some(timer) = &mut self.timer {
+            if timer.is_paused() {
+                timer.resume(now);
+            } else {
+                timer.pause(now);
+            }
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.timer.is_some_and(|timer| timer.is_paused())
+    }
+
+    /// Adds a letter to the input, returns true if it filled the row and auto submit confirmed it.
+    pub fn add_char(&mut self, c: char) -> bool {
+        if self.is_over() || self.current_guess.chars().count() >= self.word.chars().count() {
+            return false;
+        }
+        self.current_guess.push(c.to_lowercase().next().unwrap());
+        // the error was seen, it would only get in the way of the new input
+        self.reset_error();
+        if self.auto_submit && self.current_guess.chars().count() == self.word.chars().count() {
+            self.confirm();
+            return true;
+        }
+        false
+    }
+}
+
+/// Letters and feedback shown in a played board row, a forfeited turn is a row of misses.
+fn row_letters(game_state: &GameState, row: usize) -> (String, Vec<HitInfo>) {
+    match game_state.guess_in_row(row) {
+        Some(i) => (game_state.guesses[i].clone(), game_state.shown_hits(i)),
+        None => {
+            let width = game_state.word.chars().count();
+            (
+                FORFEIT.to_string().repeat(width),
+                vec![HitInfo::Miss; width],
+            )
+        }
+    }
+}
+
+/// Renders each guess as a single line of tiles, without any box drawing.
+fn render_compact<W: Write>(stdout: &mut W, game_state: &GameState) {
+    write!(
+        stdout,
+        "{}{}{}",
+        termion::clear::All,
+        termion::cursor::Hide,
+        termion::cursor::Goto(1, 1)
+    )
+    .unwrap();
+    for row in 0..game_state.turns() {
+        let (guess, hits) = row_letters(game_state, row);
+        for (c, hit) in guess.chars().zip(hits) {
+            write!(
+                stdout,
+                "{} {} {}",
+                game_state.theme.style(&hit),
+                c,
+                termion::style::Reset
+            )
+            .unwrap();
+        }
+        write!(stdout, "\r\n").unwrap();
+    }
+    if !game_state.is_over() {
+        let width = game_state.word.chars().count();
+        let mut input = game_state.current_guess.clone();
+        while input.chars().count() < width {
+            input.push('_');
+        }
+        for c in input.chars() {
+            write!(stdout, " {} ", c).unwrap();
+        }
+        write!(stdout, "\r\n").unwrap();
+    }
+    if let Some(pips) = tries_pips(game_state).filter(|_| game_state.tries_pips) {
+        write!(stdout, "Tries: {}\r\n", pips).unwrap();
+    }
+    if let Some(error) = game_state.error_message() {
+        write!(
+            stdout,
+            "{}{}{}\r\n",
+            game_state.theme.error,
+            error,
+            termion::style::Reset
+        )
+        .unwrap();
+    }
+    if game_state.show_remaining {
+        write!(
+            stdout,
+            "{} possible answers left\r\n",
+            game_state.remaining_candidate_count()
+        )
+        .unwrap();
+    }
+    if let Some(notice) = &game_state.notice {
+        write!(stdout, "{}\r\n", notice).unwrap();
+    }
+    stdout.flush().unwrap();
+}
+
+/// Number of candidates a guess ruled out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuessExplanation {
+    pub guess: String,
+    pub before: usize,
+    pub after: usize,
+}
+
+fn explanation_table(rows: &[GuessExplanation]) -> String {
+    let width = rows
+        .iter()
+        .map(|row| row.guess.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Guess".len());
+    let mut table = format!(
+        "{:<width$}  {:>6}  {:>6}  {:>10}\n",
+        "Guess",
+        "Before",
+        "After",
+        "Eliminated",
+        width = width
+    );
+    for row in rows {
+        table += &format!(
+            "{:<width$}  {:>6}  {:>6}  {:>10}\n",
+            row.guess,
+            row.before,
+            row.after,
+            row.before - row.after,
+            width = width
+        );
+    }
+    table
+}
+
+/// Grid of the letters tried at every position, next to the letter of the answer.
+fn position_letters_table(game_state: &GameState) -> String {
+    let mut table = "Letters tried per position:\n".to_string();
+    for (i, (answer, letters)) in game_state
+        .word
+        .chars()
+        .zip(game_state.attempted_letters())
+        .enumerate()
+    {
+        let letters: Vec<String> = letters
+            .iter()
+            .map(|c| c.to_uppercase().to_string())
+            .collect();
+        table += &format!(
+            "{} {} | {}\n",
+            i + 1,
+            answer.to_uppercase(),
+            letters.join(" ")
+        );
+    }
+    table
+}
+
+/// Mini board of the guesses the solver plays for the answer of `game_state`.
+fn optimal_line_board(game_state: &GameState) -> String {
+    let length = game_state.word.chars().count();
+    let candidates: Vec<String> = game_state
+        .answers
+        .iter()
+        .filter(|w| w.chars().count() == length)
+        .cloned()
+        .collect();
+    let line =
+        match solver::optimal_line(&game_state.word, &candidates, game_state.max_tries as usize) {
+            Some(line) => line,
+            None => return "The solver doesn't find the word either\n".to_string(),
+        };
+    let high_contrast = game_state.theme.name == "colorblind";
+    let mut board = format!("Solver's line ({}/{}):\n", line.len(), game_state.max_tries);
+    for guess in &line {
+        board += &format!(
+            "{} {}\n",
+            guess.to_uppercase(),
+            share::tiles(
+                &GameState::compute_hits(&game_state.word, guess),
+                high_contrast
+            )
+        );
+    }
+    board
+}
+
+/// Column the board starts in.
+const BOARD_LEFT: u16 = 10;
+
+/// Columns needed to draw the board for words of `length` letters.
+fn board_width(length: usize) -> usize {
+    BOARD_LEFT as usize - 1 + length * 2 + 1
+}
+
+fn board_fits(length: usize, terminal_width: u16) -> bool {
+    board_width(length) <= terminal_width as usize
+}
+
+fn render_game_state<W: Write>(stdout: &mut W, game_state: &GameState) {
+    if game_state.compact && !game_state.is_paused() {
+        return render_compact(stdout, game_state);
+    }
+    writeln!(stdout, "{}{}", termion::clear::All, termion::cursor::Hide).unwrap();
+    if game_state.is_paused() {
+        // the board stays hidden so the pause can't be used to think ahead
+        write!(
+            stdout,
+            "{}PAUSED - press Ctrl+T to resume",
+            termion::cursor::Goto(10, 4)
+        )
+        .unwrap();
+        stdout.flush().unwrap();
+        return;
+    }
+    let width = game_state.word.chars().count() as u16;
+    let height = game_state.max_tries;
+    let m_top = 4;
+    let m_left = BOARD_LEFT;
+    write!(stdout, "{}", termion::cursor::Goto(m_left, 1)).unwrap();
+    if game_state.show_header && game_state.turns() == 0 {
+        // the turn of a multiplayer game follows on the same line
+        write!(stdout, "{}  ", game_state.header()).unwrap();
+    }
+    if game_state.players > 1 && !game_state.is_over() {
+        write!(stdout, "Player {}'s turn", game_state.current_player() + 1).unwrap();
+    }
+    let rows = game_state.visible_rows();
+    if rows.start > 0 {
+        write!(
+            stdout,
+            "{}{} earlier guesses",
+            termion::cursor::Goto(m_left, 1),
+            rows.start
+        )
+        .unwrap();
+    }
+    if let Some(timer) = &game_state.timer {
+        let now = Instant::now();
+        write!(
+            stdout,
+            "{}Time {}",
+            termion::cursor::Goto(m_left, 2),
+            timer::format_duration(timer.elapsed(now))
+        )
+        .unwrap();
+        if let Some(remaining) = timer.turn_remaining(now).filter(|_| !game_state.is_over()) {
+            write!(stdout, "  Turn {}", timer::format_duration(remaining)).unwrap();
+        }
+    }
+    let border = &game_state.border;
+    let closest = if game_state.is_over() && !game_state.won() {
+        game_state.closest_guess()
+    } else {
+        None
+    };
+    let quality = game_state.guess_quality();
+    for y in 0..height {
+        let row = rows.start + y as usize;
+        if let Some(line) = border.line_above(y as usize, width as usize) {
+            write!(
+                stdout,
+                "{}{}",
+                termion::cursor::Goto(m_left, m_top + y * 2 - 1),
+                line
+            )
+            .unwrap();
+        }
+
+        if game_state.numbered {
+            write!(
+                stdout,
+                "{}{:>2}",
+                termion::cursor::Goto(m_left - 3, m_top + y * 2),
+                row + 1
+            )
+            .unwrap();
+        }
+
+        // get guess of line or a string of underscores
+        let line_guess: String;
+        if row < game_state.turns() {
+            line_guess = row_letters(game_state, row).0;
+        } else if row == game_state.turns() {
+            let mut curr_guess = game_state.current_guess.clone();
+            while curr_guess.chars().count() < width as usize {
+                curr_guess.push('_');
+            }
+            line_guess = curr_guess;
+        } else {
+            line_guess = (0..width).map(|_| "_").collect::<String>();
+        }
+
+        // mark the input if it can't become a valid word
+        let invalid_prefix = game_state.prefix_check
+            && row == game_state.turns()
+            && !game_state.has_valid_prefix(&game_state.current_guess);
+
+        // get hits of line
+        let line_hits: Vec<HitInfo> = if row < game_state.turns() {
+            row_letters(game_state, row).1
+        } else {
+            vec![HitInfo::None; width as usize]
+        };
+
+        for x in 0..width {
+            // print each letter into a cell
+            write!(
+                stdout,
+                "{}{}",
+                termion::cursor::Goto(m_left + x * 2, m_top + y * 2),
+                border.vertical
+            )
+            .unwrap();
+
+            // set color according to hit info, overlap guesses may be shorter than the row
+            let hit_info = line_hits.get(x as usize).unwrap_or(&HitInfo::None);
+            if hit_info == &HitInfo::None && invalid_prefix {
+                write!(stdout, "{}", game_state.theme.error).unwrap();
+            } else {
+                write!(stdout, "{}", game_state.theme.style(hit_info)).unwrap();
+            }
+
+            write!(
+                stdout,
+                "{}{}",
+                line_guess.chars().nth(x as usize).unwrap_or(' '),
+                termion::style::Reset
+            )
+            .unwrap();
+        }
+        // close cell
+        write!(stdout, "{}", border.vertical).unwrap();
+        if closest.is_some() && closest == game_state.guess_in_row(row) {
+            write!(stdout, " < closest").unwrap();
+        }
+        if let Some(quality) = quality.filter(|_| row == game_state.turns()) {
+            write!(stdout, " {}", quality_meter(quality)).unwrap();
+        }
+        writeln!(stdout).unwrap();
+    }
+    if let Some(line) = border.line_below_last(width as usize) {
+        write!(
+            stdout,
+            "{}{}",
+            termion::cursor::Goto(m_left, m_top + height * 2 - 1),
+            line
+        )
+        .unwrap();
+    }
+    // print keyboard and messages below game board
+    let mut m_bottom = m_top + height * 2;
+    if let Some(keyboard) = &game_state.keyboard {
+        render_keyboard(stdout, game_state, keyboard, m_left, m_bottom + 1);
+        m_bottom += keyboard.rows.len() as u16 + 1;
+        if game_state.keyboard_legend {
+            render_legend(stdout, game_state, m_left, m_bottom + 1);
+            m_bottom += 1;
+        }
+    }
+    if let Some(pips) = tries_pips(game_state).filter(|_| game_state.tries_pips) {
+        writeln!(
+            stdout,
+            "{}Tries: {}",
+            termion::cursor::Goto(m_left, m_bottom + 1),
+            pips
+        )
+        .unwrap();
+        m_bottom += 1;
+    }
+    if game_state.available_letters && !game_state.feedback_hidden() {
+        let letters: Vec<String> = game_state
+            .available_letters()
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        writeln!(
+            stdout,
+            "{}Letters: {}",
+            termion::cursor::Goto(m_left, m_bottom + 1),
+            letters.join(" ")
+        )
+        .unwrap();
+        m_bottom += 1;
+    }
+    if game_state.eliminated_letters && !game_state.feedback_hidden() {
+        write!(
+            stdout,
+            "{}Eliminated:",
+            termion::cursor::Goto(m_left, m_bottom + 1)
+        )
+        .unwrap();
+        for c in game_state.eliminated_letters() {
+            write!(
+                stdout,
+                " {}{}{}",
+                game_state.theme.miss,
+                c,
+                termion::style::Reset
+            )
+            .unwrap();
+        }
+        writeln!(stdout).unwrap();
+        m_bottom += 1;
+    }
+    if let Some(error) = game_state.error_message() {
+        writeln!(
+            stdout,
+            "{}{}{}{}",
+            termion::cursor::Goto(m_left, m_bottom + 1),
+            game_state.theme.error,
+            error,
+            termion::style::Reset
+        )
+        .unwrap();
+    }
+    if game_state.show_remaining {
+        writeln!(
+            stdout,
+            "{}{} possible answers left",
+            termion::cursor::Goto(m_left, m_bottom + 2),
+            game_state.remaining_candidate_count()
+        )
+        .unwrap();
+    }
+    if let Some(notice) = &game_state.notice {
+        writeln!(
+            stdout,
+            "{}{}",
+            termion::cursor::Goto(m_left, m_bottom + 3),
+            notice
+        )
+        .unwrap();
+    }
+    if let Some(frequency) = game_state.frequency.filter(|_| game_state.show_difficulty) {
+        if game_state.guesses.is_empty() {
+            writeln!(
+                stdout,
+                "{}Difficulty: {}",
+                termion::cursor::Goto(m_left, m_bottom + 4),
+                difficulty_label(frequency)
+            )
+            .unwrap();
+        }
+    }
+    if game_state.show_vowels && game_state.guesses.is_empty() {
+        writeln!(
+            stdout,
+            "{}The word has {} vowel(s)",
+            termion::cursor::Goto(m_left, m_bottom + 5),
+            words::count_vowels(&game_state.word)
+        )
+        .unwrap();
+    }
+    render_preview(stdout, game_state, m_left + width * 2 + 6, m_top - 1);
+    stdout.flush().unwrap();
+}
+
+/// Draws the keyboard with every letter colored by its best status so far.
+fn render_keyboard<W: Write>(
+    stdout: &mut W,
+    game_state: &GameState,
+    keyboard: &Keyboard,
+    m_left: u16,
+    m_top: u16,
+) {
+    let statuses = if game_state.feedback_hidden() {
+        HashMap::new()
+    } else {
+        game_state.letter_statuses()
+    };
+    for (y, row) in keyboard.rows.iter().enumerate() {
+        write!(
+            stdout,
+            "{}",
+            termion::cursor::Goto(m_left + y as u16, m_top + y as u16)
+        )
+        .unwrap();
+        for key in row {
+            let status = statuses.get(key).unwrap_or(&HitInfo::None);
+            if game_state.new_statuses.contains_key(key) {
+                write!(stdout, "{}", termion::style::Bold).unwrap();
+            }
+            write!(
+                stdout,
+                "{}{}{} ",
+                game_state.theme.style(status),
+                key,
+                termion::style::Reset
+            )
+            .unwrap();
+        }
+    }
+}
+
+/// Explains the colors of the keyboard using the tiles of the theme.
+fn render_legend<W: Write>(stdout: &mut W, game_state: &GameState, m_left: u16, m_top: u16) {
+    write!(stdout, "{}", termion::cursor::Goto(m_left, m_top)).unwrap();
+    for (hit, label) in [
+        (HitInfo::Hit, "correct"),
+        (HitInfo::Contains, "present"),
+        (HitInfo::Miss, "absent"),
+    ] {
+        write!(
+            stdout,
+            "{}   {} {}  ",
+            game_state.theme.style(&hit),
+            termion::style::Reset,
+            label
+        )
+        .unwrap();
+    }
+}
+
+/// Meter of ten cells, filled by the expected share of eliminated candidates.
+fn quality_meter(quality: f64) -> String {
+    let filled = (quality * 10.0).round() as usize;
+    format!(
+        "[{}{}]",
+        "#".repeat(filled),
+        ".".repeat(10 - filled.min(10))
+    )
+}
+
+/// Row of pips, filled for the used tries and empty for the remaining ones.
+/// None in zen mode, which has no limit.
+fn tries_pips(game_state: &GameState) -> Option<String> {
+    if game_state.zen {
+        return None;
+    }
+    let used = game_state.turns().min(game_state.max_tries as usize);
+    Some(format!(
+        "{}{}",
+        "\u{25cf}".repeat(used),
+        "\u{25cb}".repeat(game_state.remaining_tries())
+    ))
+}
+
+/// Shows how a full input would split the remaining candidates, in assist mode.
+fn render_preview<W: Write>(stdout: &mut W, game_state: &GameState, m_left: u16, m_top: u16) {
+    let length = game_state.word.chars().count();
+    if !game_state.show_preview || game_state.current_guess.chars().count() != length {
+        return;
+    }
+    let groups = game_state.preview(&game_state.current_guess);
+    write!(
+        stdout,
+        "{}\"{}\" splits the candidates into {} groups",
+        termion::cursor::Goto(m_left, m_top),
+        game_state.current_guess,
+        groups.len()
+    )
+    .unwrap();
+    let shown = 8;
+    for (i, (hits, count)) in groups.iter().take(shown).enumerate() {
+        write!(
+            stdout,
+            "{}",
+            termion::cursor::Goto(m_left, m_top + 1 + i as u16)
+        )
+        .unwrap();
+        for hit in hits {
+            write!(
+                stdout,
+                "{}  {}",
+                game_state.theme.style(hit),
+                termion::style::Reset
+            )
+            .unwrap();
+        }
+        write!(stdout, " {}", count).unwrap();
+    }
+    if groups.len() > shown {
+        write!(
+            stdout,
+            "{}... {} more",
+            termion::cursor::Goto(m_left, m_top + 1 + shown as u16),
+            groups.len() - shown
+        )
+        .unwrap();
+    }
+}
+
+fn beep<W: Write>(stdout: &mut W) {
+    write!(stdout, "\x07").unwrap();
+    stdout.flush().unwrap();
+}
+
+/// Rings the bell after a guess was confirmed: once if it was rejected, twice on a win.
+fn ring_bell<W: Write>(stdout: &mut W, game_state: &GameState) {
+    if !game_state.bell {
+        return;
+    }
+    if game_state.last_error.is_some() {
+        beep(stdout);
+    } else if game_state.is_over() && game_state.won() {
+        beep(stdout);
+        std::thread::sleep(Duration::from_millis(200));
+        beep(stdout);
+    }
+}
+
+/// Reveals the tiles of the guess just confirmed one at a time.
+fn animate_reveal<W: Write>(stdout: &mut W, game_state: &mut GameState) {
+    if !game_state.animate || game_state.last_error.is_some() || game_state.feedback_hidden() {
+        return;
+    }
+    let Some(last) = game_state.guesses.len().checked_sub(1) else {
+        return;
+    };
+    let order = game_state.reveal_order(last);
+    for shown in 0..order.len() {
+        game_state.revealing = Some(order[..shown].to_vec());
+        render(stdout, game_state);
+        std::thread::sleep(Duration::from_millis(150));
+    }
+    game_state.revealing = None;
+}
+
+/// Shows in which attempt every position was found, as a row of tiles.
+fn render_first_hits<W: Write>(stdout: &mut W, game_state: &GameState) {
+    if game_state.guesses.is_empty() {
+        return;
+    }
+    write!(stdout, "Found in attempt: ").unwrap();
+    for first in game_state.first_hits() {
+        let (style, label) = match first {
+            Some(attempt) => (&game_state.theme.hit, attempt.to_string()),
+            None => (&game_state.theme.miss, "-".to_string()),
+        };
+        write!(stdout, "{} {} {}", style, label, termion::style::Reset).unwrap();
+    }
+    write!(stdout, "\r\n").unwrap();
+}
+
+/// Renders the board and updates the broadcast file if there is one.
+/// The frame is written at once, which avoids flicker from many small writes.
+fn render<W: Write>(stdout: &mut W, game_state: &GameState) {
+    let mut frame = Vec::new();
+    render_game_state(&mut frame, game_state);
+    stdout.write_all(&frame).unwrap();
+    stdout.flush().unwrap();
+    if let Some(path) = &game_state.broadcast {
+        // the overlay is optional, a failed update must not end the game
+        let _ = broadcast::write(path, game_state);
+    }
+}
+
+fn game_loop(mut game_state: GameState) -> Result<GameState, RustleError> {
+    let mut stdin = stdin().keys();
+    let mut stdout = stdout().into_raw_mode().map_err(RustleError::Terminal)?;
+    // timed games poll the terminal so the clock keeps running without input
+    let mut tty = match game_state.timer {
+        Some(_) => {
+            let tty = termion::get_tty().map_err(RustleError::Terminal)?;
+            let keys = tty.try_clone().map_err(RustleError::Terminal)?.keys();
+            Some((tty, keys))
+        }
+        None => None,
+    };
+    if let Some(timer) = &mut game_state.timer {
+        *timer = Timer::new(Instant::now(), timer.turn_limit);
+    }
+    'game_loop: while !game_state.is_over() {
+        game_state.auto_hint();
+        render(&mut stdout, &game_state);
+        // the emphasis is only shown once, right after the guess
+        game_state.new_statuses.clear();
+        'input_loop: loop {
+            let b = match &mut tty {
+                Some((tty, keys)) => {
+                    if !timer::wait_for_input(tty, Duration::from_millis(250)) {
+                        if game_state.check_turn_timer(Instant::now()) {
+                            break 'input_loop;
+                        }
+                        render(&mut stdout, &game_state);
+                        continue;
+                    }
+                    keys.next()
+                }
+                None => stdin.next(),
+            };
+            // the input was closed, end the game like Esc
+            let b = match b {
+                Some(key) => key.map_err(RustleError::Terminal)?,
+                None => break 'game_loop,
+            };
+            match b {
+                Key::Esc => break 'game_loop,
+                Key::Ctrl('t') => game_state.toggle_pause(Instant::now()),
+                Key::F(2) => game_state.next_theme(),
+                _ if game_state.is_paused() => (),
+                Key::Backspace => game_state.back(),
+                Key::Char('\t') => game_state.reveal_remaining(),
+                Key::Ctrl('p') => game_state.toggle_preview(),
+                Key::Ctrl('r') => {
+                    game_state.notice = Some(match game_state.reload_words() {
+                        Ok(count) => format!("Reloaded {} words", count),
+                        Err(error) => format!("Could not reload word list: {}", error),
+                    });
+                }
+                Key::Ctrl('a') => {
+                    game_state.reveal_absent_letter();
+                }
+                Key::Ctrl('z') => {
+                    game_state.undo_last_guess();
+                }
+                Key::Ctrl('d') => {
+                    if let Some(answer) = game_state.debug_answer() {
+                        eprint!("{}\r\n", answer);
+                    }
+                }
+                Key::Char(c) => {
+                    let guesses = game_state.guesses.len();
+                    // a full row is confirmed right away with auto submit
+                    let confirmed = if c == '\n' {
+                        game_state.confirm();
+                        true
+                    } else {
+                        game_state.add_char(c)
+                    };
+                    if confirmed {
+                        if game_state.guesses.len() > guesses {
+                            animate_reveal(&mut stdout, &mut game_state);
+                        }
+                        ring_bell(&mut stdout, &game_state);
+                        break 'input_loop;
+                    }
+                }
+                _ => (),
+            }
+            render(&mut stdout, &game_state);
+        }
+
+        if game_state.last_error.is_none() && game_state.is_over() && game_state.won() {
+            match game_state.solving_player() {
+                Some(player) if game_state.players > 1 => {
+                    println!("Player {} solved it!", player + 1)
+                }
+                _ => println!("You won!"),
+            }
+            break;
+        }
+    }
+
+    render(&mut stdout, &game_state);
+    writeln!(stdout, "{}", termion::cursor::Show).unwrap();
+    match game_state.reveal_gate() {
+        RevealDelay::Immediate => (),
+        RevealDelay::Pause(delay) => {
+            write!(stdout, "\rThe word was ...").unwrap();
+            stdout.flush().unwrap();
+            std::thread::sleep(delay);
+        }
+        RevealDelay::Key => {
+            write!(stdout, "\rPress any key to reveal the word").unwrap();
+            stdout.flush().unwrap();
+            match &mut tty {
+                Some((_, keys)) => keys.next(),
+                None => stdin.next(),
+            };
+        }
+    }
+    if game_state.reveal_gate() != RevealDelay::Immediate {
+        write!(stdout, "\r{}", termion::clear::CurrentLine).unwrap();
+    }
+    if !game_state.won() {
+        println!("You lost! The word was: {}", game_state.word);
+        if let Some(closest) = game_state.closest_guess() {
+            println!("Closest guess: {}", game_state.guesses[closest]);
+        }
+    }
+    if let Some(daily) = game_state.daily {
+        if !game_state.counts_toward_streak() {
+            println!(
+                "Archive puzzle of {}, it doesn't count towards the daily streak",
+                daily.date
+            );
+        }
+    }
+    if game_state.remaining_reveals > 0 {
+        println!(
+            "Remaining answers were revealed {} time(s)",
+            game_state.remaining_reveals
+        );
+    }
+    if !game_state.revealed_absent.is_empty() {
+        println!(
+            "Absent letters were revealed {} time(s)",
+            game_state.revealed_absent.len()
+        );
+    }
+    render_first_hits(&mut stdout, &game_state);
+    if let Some(spec) = game_state.challenge {
+        let beaten = match spec.guesses {
+            Some(guesses) => game_state.won() && game_state.turns() < guesses,
+            None => game_state.won(),
+        };
+        if beaten {
+            write!(stdout, "You beat the challenge!\r\n").unwrap();
+        }
+    }
+    if let Some(id) = game_state.game_id() {
+        write!(stdout, "Game: {}\r\n", id).unwrap();
+    }
+    if let Some(code) = challenge::encode_challenge(&game_state) {
+        write!(stdout, "Challenge code: {}\r\n", code).unwrap();
+    }
+    if !game_state.guess_times.is_empty() {
+        let times: Vec<String> = game_state
+            .guess_times
+            .iter()
+            .map(|time| timer::format_duration(*time))
+            .collect();
+        write!(stdout, "Time per guess: {}\r\n", times.join(" ")).unwrap();
+    }
+    if game_state.explain && game_state.assist {
+        // still in raw mode, so lines need a carriage return
+        for line in explanation_table(&game_state.explanation()).lines() {
+            write!(stdout, "{}\r\n", line).unwrap();
+        }
+    }
+    if game_state.position_letters {
+        for line in position_letters_table(&game_state).lines() {
+            write!(stdout, "{}\r\n", line).unwrap();
+        }
+    }
+    if game_state.optimal_line {
+        for line in optimal_line_board(&game_state).lines() {
+            write!(stdout, "{}\r\n", line).unwrap();
+        }
+    }
+    Ok(game_state)
+}
+
+/// Word lists and answer selection of a new game.
+#[derive(Debug, Default, Clone, Copy)]
+struct GameOptions<'a> {
+    any_word: bool,
+    /// word lists combined by `word_sources`, the embedded lists if empty
+    word_files: &'a [String],
+    word_sources: words::CompositePolicy,
+    alphabet: Option<&'a str>,
+    /// words which are always possible answers
+    favorites: &'a [String],
+    /// preferred answer length
+    length: Option<usize>,
+    /// fixed answer instead of a random one, e.g. for the daily puzzle
+    answer_index: Option<usize>,
+    /// the answer index is the number of a daily puzzle in the rotating schedule of this season
+    daily_season: Option<u64>,
+    /// answers to avoid
+    recent: &'a [String],
+    /// occurrences per million below which words aren't accepted as guesses
+    min_frequency: Option<f64>,
+    /// replays the random answer of an earlier game
+    seed: Option<u64>,
+    /// only pick answers which can be guessed without repeating a letter
+    unique_letters: bool,
+}
+
+/// Restrictions of the words accepted as guesses, applied again when the word files are reloaded.
+#[derive(Debug, Clone, Default)]
+struct WordFilters {
+    /// only words made of these letters are used
+    alphabet: Option<String>,
+    /// words which are always possible answers
+    favorites: Vec<String>,
+    /// occurrences per million below which words aren't accepted as guesses
+    min_frequency: Option<f64>,
+}
+
+impl WordFilters {
+    /// Keeps the words made of the letters of the alphabet, all without one.
+    fn filter_alphabet(&self, words: Vec<String>) -> Vec<String> {
+        match &self.alphabet {
+            Some(alphabet) => words::filter_alphabet(words, alphabet),
+            None => words,
+        }
+    }
+
+    /// Favorites which can be used with the alphabet.
+    fn favorites(&self) -> Vec<String> {
+        self.filter_alphabet(self.favorites.clone())
+    }
+
+    /// Words of `words` accepted as guesses, plus the favorites among the `answers`.
+    /// Rare words are dropped, but `answer` is always accepted.
+    fn accepted_guesses(
+        &self,
+        words: Vec<String>,
+        answers: &[String],
+        frequencies: &HashMap<String, f64>,
+        answer: &str,
+    ) -> Vec<String> {
+        let mut words = self.filter_alphabet(words);
+        for favorite in self.favorites() {
+            if answers.contains(&favorite) && !words.contains(&favorite) {
+                words.push(favorite);
+            }
+        }
+        if let Some(min) = self.min_frequency {
+            words = words::filter_min_frequency(words, frequencies, min);
+        }
+        if !words.iter().any(|w| w == answer) {
+            words.push(answer.to_string());
+        }
+        words
+    }
+}
+
+fn init_game<R: Rng>(options: &GameOptions, rng: &mut R) -> io::Result<GameState> {
+    let GameOptions {
+        any_word,
+        word_files,
+        word_sources,
+        alphabet,
+        favorites,
+        length,
+        answer_index,
+        daily_season,
+        recent,
+        min_frequency,
+        seed,
+        unique_letters,
+    } = *options;
+    let filters = WordFilters {
+        alphabet: alphabet.map(str::to_string),
+        favorites: favorites.to_vec(),
+        min_frequency,
+    };
+    // words acceptable as solutions and all words accepted as guesses
+    let embedded = [words::EMBEDDED_SOURCE.to_string()];
+    let sources = if word_files.is_empty() {
+        &embedded[..]
+    } else {
+        word_files
+    };
+    let list = words::CompositeWordProvider::for_sources(sources, word_sources).resolve()?;
+    let (mut answers, frequencies) = words::split_frequencies(list.words);
+    let mut words = answers.clone();
+    words.extend(list.extra_guesses);
+
+    answers = filters.filter_alphabet(answers);
+    if let Some(alphabet) = alphabet {
+        if answers.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("no words use only the letters \"{}\"", alphabet),
+            ));
+        }
+    }
+    if answers.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "word list is empty",
+        ));
+    }
+    if let Some(length) = length {
+        // the preferred length is ignored if the list has no such words
+        if answers.iter().any(|w| w.chars().count() == length) {
+            answers.retain(|w| w.chars().count() == length);
+        }
+    }
+    if unique_letters && answers.iter().any(|w| !words::has_repeated_letters(w)) {
+        answers.retain(|w| !words::has_repeated_letters(w));
+    }
+    words::merge_favorites(&mut answers, &filters.favorites());
+
+    let (i, seed) = match (answer_index, seed) {
+        (Some(index), _) => match daily_season {
+            Some(season) => (daily::scheduled_index(index, answers.len(), season), None),
+            None => (index % answers.len(), None),
+        },
+        (None, Some(seed)) => (answer_for_seed(&answers, &frequencies, seed), Some(seed)),
+        (None, None) => {
+            let (index, seed) = pick_answer(&answers, &frequencies, recent, rng);
+            (index, Some(seed))
+        }
+    };
+    let word = answers[i].clone();
+    // rare words are only dropped from the guesses, the answer stays valid
+    let words = filters.accepted_guesses(words, &answers, &frequencies, &word);
+
+    let mut game_state = GameState::new(word, words, any_word);
+    game_state.word_list = list.name;
+    game_state.word_files = word_files.to_vec();
+    game_state.word_sources = word_sources;
+    game_state.word_filters = filters;
+    game_state.daily_season = daily_season.filter(|_| answer_index.is_some());
+    game_state.frequency = frequencies.get(&game_state.word).copied();
+    game_state.seed = seed;
+    game_state.answers = answers;
+    Ok(game_state)
+}
+
+/// Points `options` and the daily puzzle at the puzzle of a challenge code.
+fn apply_challenge(
+    spec: &ChallengeSpec,
+    options: &mut GameOptions,
+    today: Date,
+) -> Result<Option<DailyGame>, String> {
+    options.length = Some(spec.length);
+    match spec.puzzle {
+        Puzzle::Answer(index) => {
+            options.answer_index = Some(index);
+            Ok(None)
+        }
+        Puzzle::Daily(index) => {
+            let daily =
+                DailyGame::for_date(daily_date(index), today).map_err(|error| error.to_string())?;
+            options.answer_index = Some(daily.index);
+            Ok(Some(daily))
+        }
+    }
+}
+
+/// Checks that the game started from `spec` has the puzzle the sender played.
+fn check_challenge(spec: &ChallengeSpec, game_state: &GameState) -> Result<(), String> {
+    let in_list = match spec.puzzle {
+        Puzzle::Answer(index) => index < game_state.answers.len(),
+        Puzzle::Daily(_) => true,
+    };
+    if !in_list || game_state.word.chars().count() != spec.length {
+        return Err("Challenge code doesn't match this word list".to_string());
+    }
+    Ok(())
+}
+
+/// Plays `guesses` without a terminal UI and returns the rejected guesses and the final board.
+fn play_guesses(game_state: &mut GameState, guesses: &[&str]) -> String {
+    let mut output = String::new();
+    for guess in guesses {
+        if game_state.is_over() {
+            output.push_str(&format!("{}: the game is already over\n", guess));
+            continue;
+        }
+        game_state.current_guess = guess.to_lowercase();
+        game_state.confirm();
+        if let Some(error) = game_state.error_message() {
+            output.push_str(&format!("{}: {}\n", guess, error));
+        }
+    }
+    output.push_str(&broadcast::board_text(game_state));
+    if let Some(id) = game_state.game_id() {
+        output.push_str(&format!("game: {}\n", id));
+    }
+    output
+}
+
+/// What the program does with the game it set up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /// a game played by the local player
+    Local,
+    /// the `GUESS` arguments are played and the board printed
+    Guesses,
+    Serve,
+    Connect,
+    Benchmark,
+    Watch,
+    Duel,
+}
+
+impl Mode {
+    fn from_matches(matches: &ArgMatches) -> Mode {
+        if matches.is_present("guesses") {
+            Mode::Guesses
+        } else if matches.is_present("serve") {
+            Mode::Serve
+        } else if matches.is_present("connect") {
+            Mode::Connect
+        } else if matches.is_present("benchmark") {
+            Mode::Benchmark
+        } else if matches.is_present("watch") {
+            Mode::Watch
+        } else if matches.is_present("duel") {
+            Mode::Duel
+        } else {
+            Mode::Local
+        }
+    }
+
+    /// Option starting the mode, for modes which can only be shown in a terminal.
+    fn terminal_option(self) -> Option<&'static str> {
+        match self {
+            Mode::Connect => Some("--connect"),
+            Mode::Watch => Some("--watch"),
+            Mode::Duel => Some("--duel"),
+            _ => None,
+        }
+    }
+}
+
+/// How the game is shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Interface {
+    /// the board in a terminal in raw mode
+    Terminal,
+    /// line based text, with the reason the board can't be shown
+    Plain(&'static str),
+    /// nothing is played interactively, the output is plain text
+    Headless,
+}
+
+/// Interface of `mode`, modes which need a terminal fail without one
+/// instead of falling back to a plain local game.
+fn choose_interface(mode: Mode, tty: bool, raw_mode: bool) -> Result<Interface, RustleError> {
+    if matches!(mode, Mode::Guesses | Mode::Serve | Mode::Benchmark) {
+        return Ok(Interface::Headless);
+    }
+    let reason = if !tty {
+        "Not a terminal"
+    } else if !raw_mode {
+        "The terminal doesn't support raw mode"
+    } else {
+        return Ok(Interface::Terminal);
+    };
+    match mode.terminal_option() {
+        Some(option) => Err(RustleError::Usage(format!(
+            "{}, {} needs a terminal",
+            reason, option
+        ))),
+        None => Ok(Interface::Plain(reason)),
+    }
+}
+
+/// Plays a game reading one guess per line, for input and output which aren't a terminal.
+/// Every guess is answered with its feedback, `G` for a hit, `Y` for a letter
+/// in the wrong place and `.` for a miss.
+fn plain_game<R: BufRead, W: Write>(game_state: &mut GameState, input: R, output: &mut W) {
+    writeln!(
+        output,
+        "Guess the {} letter word, one guess per line",
+        game_state.word.chars().count()
+    )
+    .unwrap();
+    let mut lines = input.lines();
+    while !game_state.is_over() {
+        let Some(Ok(line)) = lines.next() else {
+            break;
+        };
+        game_state.current_guess = line.trim().to_lowercase();
+        game_state.confirm();
+        match game_state.error_message() {
+            Some(error) => writeln!(output, "{}: {}", line.trim(), error).unwrap(),
+            None => {
+                let last = game_state.guesses.len() - 1;
+                writeln!(
+                    output,
+                    "{} {}",
+                    game_state.guesses[last],
+                    protocol::encode_hits(&game_state.shown_hits(last))
+                )
+                .unwrap();
+            }
+        }
+    }
+    if game_state.won() {
+        writeln!(output, "You won in {} guesses!", game_state.turns()).unwrap();
+    } else if game_state.is_over() {
+        writeln!(output, "You lost! The word was: {}", game_state.word).unwrap();
+    } else {
+        writeln!(output, "Stopped, the word was: {}", game_state.word).unwrap();
+    }
+}
+
+/// Uses the answer given by `RUSTLE_DAILY_WORD` instead of the daily pick, if it is valid.
+fn apply_daily_word(game_state: &mut GameState, daily_word: Option<&str>) {
+    if let Some(word) = daily_word {
+        if let Err(error) = game_state.set_word(word) {
+            eprintln!("Ignoring RUSTLE_DAILY_WORD \"{}\": {}", word, error);
+        }
+    }
+}
+
+/// Whether the value of `RUSTLE_DEBUG` enables the debug command.
+fn debug_enabled(value: Option<&str>) -> bool {
+    value == Some("1")
+}
+
+/// Rough difficulty of a word by its frequency in occurrences per million words.
+fn difficulty_label(frequency: f64) -> &'static str {
+    if frequency >= 10.0 {
+        "common"
+    } else if frequency >= 1.0 {
+        "uncommon"
+    } else {
+        "rare"
+    }
+}
+
+/// Picks the index of the answer for `seed`, weighted by word frequency if any are known.
+fn answer_for_seed(answers: &[String], frequencies: &HashMap<String, f64>, seed: u64) -> usize {
+    let mut rng = StdRng::seed_from_u64(seed);
+    if !frequencies.is_empty() {
+        // words without a frequency are weighted like a frequency of 1
+        let weights = answers
+            .iter()
+            .map(|answer| *frequencies.get(answer).unwrap_or(&1.0));
+        if let Ok(index) = rand::distributions::WeightedIndex::new(weights) {
+            return index.sample(&mut rng);
+        }
+    }
+    rng.gen::<usize>() % answers.len()
+}
+
+/// Draws seeds until one picks an answer which isn't one of the `recent` answers.
+/// Returns the index of the answer and its seed.
+fn pick_answer<R: Rng>(
+    answers: &[String],
+    frequencies: &HashMap<String, f64>,
+    recent: &[String],
+    rng: &mut R,
+) -> (usize, u64) {
+    // repeats can't be avoided if every answer was used recently
+    let avoid = answers.iter().any(|answer| !recent.contains(answer));
+    let mut seed = rng.gen::<u32>() as u64;
+    // the seed alone has to identify the answer, so instead of skipping
+    // recent answers another seed is drawn
+    for _ in 0..1000 {
+        let index = answer_for_seed(answers, frequencies, seed);
+        if !avoid || !recent.contains(&answers[index]) {
+            break;
+        }
+        seed = rng.gen::<u32>() as u64;
+    }
+    (answer_for_seed(answers, frequencies, seed), seed)
+}
+
+/// Creates a game configured by the command line options.
+/// `recent` answers are avoided unless the word list is too small.
+/// Only the `first` game is picked by the challenge code or seed, later ones get a new word.
+fn new_game(
+    matches: &ArgMatches,
+    config: &Config,
+    recent: &[String],
+    first: bool,
+) -> Result<GameState, RustleError> {
+    let favorites = match matches.value_of("favorites").map(words::load_word_file) {
+        Some(Ok(favorites)) => words::split_frequencies(favorites).0,
+        Some(Err(error)) => {
+            return Err(RustleError::WordList {
+                list: "favorite words",
+                error,
+            })
+        }
+        None => Vec::new(),
+    };
+    let min_frequency = matches
+        .value_of("min-freq")
+        .map(|min| match min.parse::<f64>() {
+            Ok(min) if min >= 0.0 => Ok(min),
+            _ => Err(RustleError::Usage(format!(
+                "Invalid minimum frequency: {}",
+                min
+            ))),
+        })
+        .transpose()?;
+    let seed = matches
+        .value_of("seed")
+        .filter(|_| first)
+        .map(|seed| {
+            seed.parse::<u64>()
+                .map_err(|_| RustleError::Usage(format!("Invalid seed: {}", seed)))
+        })
+        .transpose()?;
+    let daily_season = matches
+        .value_of("daily-season")
+        .map(|season| {
+            season
+                .parse::<u64>()
+                .map_err(|_| RustleError::Usage(format!("Invalid season: {}", season)))
+        })
+        .transpose()?;
+    let today = Date::today();
+    let mut daily = match matches.value_of("daily-date") {
+        Some(date) => Some(
+            Date::parse(date)
+                .and_then(|date| DailyGame::for_date(date, today))
+                .map_err(|error| RustleError::Usage(error.to_string()))?,
+        ),
+        None if matches.is_present("daily") => DailyGame::for_date(today, today).ok(),
+        None => None,
+    };
+    let word_files: Vec<String> = matches
+        .values_of("word-file")
+        .map(|files| files.map(str::to_string).collect())
+        .unwrap_or_default();
+    let word_sources = matches
+        .value_of("word-sources")
+        .map(str::parse)
+        .transpose()
+        .map_err(RustleError::Usage)?
+        .unwrap_or_default();
+    let mut options = GameOptions {
+        any_word: matches.is_present("any-word"),
+        word_files: &word_files,
+        word_sources,
+        alphabet: matches.value_of("alphabet"),
+        favorites: &favorites,
+        length: Some(config.word_length),
+        answer_index: daily.map(|daily| daily.index),
+        daily_season,
+        recent,
+        min_frequency,
+        seed,
+        unique_letters: matches.is_present("unique-letters"),
+    };
+    let challenge = matches
+        .value_of("challenge")
+        .filter(|_| first)
+        .map(challenge::decode_challenge)
+        .transpose()
+        .map_err(|error| RustleError::Usage(error.to_string()))?;
+    if let Some(spec) = &challenge {
+        daily = apply_challenge(spec, &mut options, today).map_err(RustleError::Usage)?;
+    }
+    if daily.is_none() {
+        options.daily_season = None;
+    }
+    let mut game_state =
+        init_game(&options, &mut rand::thread_rng()).map_err(|error| RustleError::WordList {
+            list: "word list",
+            error,
+        })?;
+    if let Some(spec) = &challenge {
+        check_challenge(spec, &game_state).map_err(RustleError::Usage)?;
+        game_state.notice = Some(match spec.guesses {
+            Some(guesses) => format!("Challenge: beat {} guesses", guesses),
+            None => "Challenge: the sender didn't find this word".to_string(),
+        });
+    }
+    game_state.challenge = challenge;
+    game_state.daily = daily;
+    if daily.is_some() {
+        let daily_word = std::env::var("RUSTLE_DAILY_WORD").ok();
+        apply_daily_word(&mut game_state, daily_word.as_deref());
+    }
+    game_state.debug = debug_enabled(std::env::var("RUSTLE_DEBUG").ok().as_deref());
+    if let Some(answer) = matches.value_of("answer") {
+        if let Err(error) = game_state.set_word(answer) {
+            return Err(RustleError::Usage(format!(
+                "Invalid answer \"{}\": {}",
+                answer, error
+            )));
+        }
+    }
+    game_state.overlap = matches.is_present("overlap");
+    game_state.prefix_check = matches.is_present("prefix-check");
+    game_state.auto_submit = matches.is_present("auto-submit");
+    game_state.zen = matches.is_present("zen");
+    game_state.concealed = matches.is_present("concealed");
+    game_state.bell = matches.is_present("bell");
+    if let Some(path) = matches.value_of("banned-openers") {
+        game_state.banned_openers =
+            words::load_word_file(path).map_err(|error| RustleError::WordList {
+                list: "banned openers",
+                error,
+            })?;
+    } else if matches.is_present("ban-openers") {
+        game_state.banned_openers = BANNED_OPENERS.iter().map(|w| w.to_string()).collect();
+    }
+    if matches.is_present("binary") {
+        game_state.feedback = FeedbackStrategy::Binary;
+    }
+    if matches.is_present("binary-after-opener") {
+        game_state.feedback = FeedbackStrategy::BinaryAfterOpener;
+    }
+    game_state.numbered = matches.is_present("numbered");
+    game_state.unique_letters = matches.is_present("unique-letters");
+    game_state.anagram = matches.is_present("anagram");
+    game_state.forbid_answer_opener = matches.is_present("forbid-answer-opener");
+    game_state.auto_hint = matches.is_present("auto-hint");
+    if matches.is_present("mulligan") {
+        game_state.max_mulligans = 1;
+    }
+    if let Some(penalty) = matches.value_of("invalid-penalty") {
+        game_state.invalid_penalty = match penalty.parse::<u32>() {
+            Ok(penalty) if penalty > 0 => Some(penalty),
+            _ => return Err(RustleError::Usage(format!("Invalid penalty: {}", penalty))),
+        };
+    }
+    if let Some(delay) = matches.value_of("reveal-delay") {
+        game_state.reveal_delay = RevealDelay::parse(delay)
+            .ok_or_else(|| RustleError::Usage(format!("Invalid reveal delay: {}", delay)))?;
+    }
+    game_state.animate = matches.is_present("animate");
+    game_state.random_reveal = matches.is_present("random-reveal");
+    game_state.show_difficulty = matches.is_present("difficulty");
+    game_state.show_vowels = matches.is_present("vowels");
+    game_state.hard_mode = matches.is_present("hard") || config.hard_mode;
+    let theme = if matches.occurrences_of("theme") > 0 {
+        matches.value_of("theme").unwrap()
+    } else {
+        config.theme.as_str()
+    };
+    game_state.theme = Theme::from_name(theme).unwrap_or_else(Theme::detect);
+    game_state.border = BorderStyle::from_name(matches.value_of("border").unwrap())
+        .unwrap_or_else(BorderStyle::ascii);
+    game_state.compact = matches.is_present("compact");
+    if let Ok((terminal_width, _)) = termion::terminal_size() {
+        if !game_state.compact && !board_fits(game_state.word.chars().count(), terminal_width) {
+            game_state.compact = true;
+            game_state.notice = Some("The board is too wide, using the compact view".to_string());
+        }
+    }
+    let keyboard = if config.keyboard_layout.is_empty() {
+        Keyboard::for_words(&game_state.valid_words)
+    } else {
+        Keyboard::from_rows(&config.keyboard_layout)
+    };
+    game_state.highlight_new = matches.is_present("highlight-new");
+    if matches.is_present("no-keyboard") {
+        game_state.keyboard = None;
+    } else if matches.is_present("shuffle-keyboard") {
+        // seeded once per game so the layout stays put while playing
+        let mut rng = StdRng::seed_from_u64(rand::random());
+        game_state.keyboard = Some(keyboard.shuffled(&mut rng));
+    } else {
+        game_state.keyboard = Some(keyboard);
+    }
+    game_state.keyboard_legend = config.keyboard_legend;
+    game_state.available_letters = config.available_letters;
+    game_state.eliminated_letters = config.eliminated_letters;
+    game_state.tries_pips = config.tries_pips;
+    game_state.show_header = config.header;
+    game_state.ignore_partial_enter = config.ignore_partial_enter;
+    game_state.keep_invalid_input = config.keep_invalid_input;
+    game_state.suggest_words = config.suggest_words;
+    game_state.assist = matches.is_present("assist");
+    game_state.explain = matches.is_present("explain");
+    game_state.optimal_line = matches.is_present("optimal-line");
+    game_state.position_letters = matches.is_present("position-letters");
+    game_state.quality = matches.is_present("quality");
+    if matches.is_present("timed") || matches.is_present("turn-limit") {
+        let turn_limit = matches
+            .value_of("turn-limit")
+            .map(|seconds| match seconds.parse::<u64>() {
+                Ok(seconds) if seconds > 0 => Ok(Duration::from_secs(seconds)),
+                _ => Err(RustleError::Usage(format!(
+                    "Invalid turn limit: {}",
+                    seconds
+                ))),
+            })
+            .transpose()?;
+        game_state.timer = Some(Timer::new(Instant::now(), turn_limit));
+    }
+    game_state.broadcast = matches.value_of("broadcast").map(std::path::PathBuf::from);
+    if matches.is_present("coop") {
+        game_state.players = 2;
+    }
+    Ok(game_state)
+}
+
+/// Loads the config, the setup wizard only runs for the first game in a terminal.
+fn load_config(terminal: bool) -> Config {
+    let path = match config::config_path() {
+        Some(path) => path,
+        None => return Config::default(),
+    };
+    if path.exists() {
+        return match Config::load(&path) {
+            Ok(config) => config,
+            Err(error) => {
+                eprintln!("Could not load config: {}", error);
+                Config::default()
+            }
+        };
+    }
+    if !terminal {
+        return Config::default();
+    }
+    let config = wizard::run();
+    if let Err(error) = config.save(&path) {
+        eprintln!("Could not save config: {}", error);
+    }
+    config
+}
+
+enum NextGame {
+    New,
+    Retry,
+    Quit,
+}
+
+/// Tries after pressing + (`more`) or - once, kept within `MIN_TRIES..=MAX_TRIES`.
+fn adjust_tries(tries: u16, more: bool) -> u16 {
+    let tries = if more {
+        tries.saturating_add(1)
+    } else {
+        tries.saturating_sub(1)
+    };
+    tries.clamp(MIN_TRIES, MAX_TRIES)
+}
+
+/// Asks whether to start another game, Enter starts one and Esc quits.
+/// After a loss R retries the same word. + and - change the `tries` of new games.
+/// After `screensaver` without input the screensaver runs until a key is pressed.
+fn play_again(
+    lost: bool,
+    tries: &mut u16,
+    screensaver: Option<Duration>,
+    theme: &Theme,
+) -> Result<NextGame, RustleError> {
+    let mut stdout = stdout().into_raw_mode().map_err(RustleError::Terminal)?;
+    let prompt = |stdout: &mut termion::raw::RawTerminal<io::Stdout>, tries: u16| {
+        if lost {
+            write!(
+                stdout,
+                "\r\nPress Enter to play again, R to retry the word or Esc to quit\r\n"
+            )
+            .unwrap();
+        } else {
+            write!(stdout, "\r\nPress Enter to play again or Esc to quit\r\n").unwrap();
+        }
+        write!(stdout, "Tries: {} (+/- to change)", tries).unwrap();
+        stdout.flush().unwrap();
+    };
+    prompt(&mut stdout, *tries);
+    let mut stdin = stdin().keys();
+    // the screensaver polls the terminal to notice input without reading it
+    let mut tty = match screensaver {
+        Some(_) => {
+            let tty = termion::get_tty().map_err(RustleError::Terminal)?;
+            let keys = tty.try_clone().map_err(RustleError::Terminal)?.keys();
+            Some((tty, keys))
+        }
+        None => None,
+    };
+    loop {
+        let key = match (&mut tty, screensaver) {
+            (Some((tty, keys)), Some(idle)) => {
+                if !timer::wait_for_input(tty, idle) {
+                    screensaver::run(&mut stdout, tty, theme);
+                    // the key which ended the screensaver only returns to the prompt
+                    keys.next();
+                    prompt(&mut stdout, *tries);
+                    continue;
+                }
+                keys.next()
+            }
+            _ => stdin.next(),
+        };
+        let key = match key {
+            Some(key) => key.map_err(RustleError::Terminal)?,
+            None => return Ok(NextGame::Quit),
+        };
+        if let Key::Char(c @ ('+' | '-')) = key {
+            *tries = adjust_tries(*tries, c == '+');
+            write!(
+                stdout,
+                "\r{}Tries: {} (+/- to change)",
+                termion::clear::CurrentLine,
+                tries
+            )
+            .unwrap();
+            stdout.flush().unwrap();
+            continue;
+        }
+        let next = match key {
+            Key::Char('\n') => NextGame::New,
+            Key::Char('r') | Key::Char('R') if lost => NextGame::Retry,
+            Key::Esc => NextGame::Quit,
+            _ => continue,
+        };
+        // leave the line of the tries before the next game prints anything
+        write!(stdout, "\r\n").unwrap();
+        return Ok(next);
+    }
+}
+
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Version shown by `--version`, includes the size of the embedded word list.
+fn version_info() -> String {
+    format!("{} ({} words)", VERSION, words::embedded_word_count())
+}
+
+/// Writes the decisions of the solver to the path of `--solver-log`.
+fn write_solver_log(
+    matches: &ArgMatches,
+    decisions: Option<Vec<solver::Decision>>,
+) -> Result<(), RustleError> {
+    if let (Some(path), Some(decisions)) = (matches.value_of("solver-log"), decisions) {
+        solver::write_log(std::path::Path::new(path), &decisions)?;
+    }
+    Ok(())
+}
+
+/// Command line interface, `version` is shown by `--version`.
+fn cli(version: &str) -> App<'_> {
+    App::new("Rordle")
+        .version(version)
+        .author("Niko Abeler <niko@rerere.org>")
+        .about("A Wordle clone for the terminal")
+        .arg(
+            Arg::new("any-word")
+                .short('a')
+                .long("any-word")
+                .takes_value(false)
+                .help("Allow any word to be guessed"),
+        )
+        .arg(
+            Arg::new("overlap")
+                .long("overlap")
+                .takes_value(false)
+                .requires("any-word")
+                .help("Accept guesses of another length, comparing the letters both words have. The board only takes shorter guesses, longer ones need GUESS arguments"),
+        )
+        .arg(
+            Arg::new("word-file")
+                .short('w')
+                .long("word-file")
+                .alias("words")
+                .takes_value(true)
+                .multiple_occurrences(true)
+                .help(
+                    "Use a word list from a file (may be gzip compressed), may be repeated, \
+                     \"embedded\" is the built-in list",
+                ),
+        )
+        .arg(
+            Arg::new("word-sources")
+                .long("word-sources")
+                .takes_value(true)
+                .value_name("POLICY")
+                .possible_values(["fallback", "merge"])
+                .requires("word-file")
+                .help("Use the first word file which loads, or merge all of them"),
+        )
+        .arg(
+            Arg::new("answer")
+                .long("answer")
+                .takes_value(true)
+                .value_name("WORD")
+                .help("Use WORD as the answer"),
+        )
+        .arg(
+            Arg::new("avoid-recent")
+                .long("avoid-recent")
+                .takes_value(true)
+                .value_name("N")
+                .default_value("10")
+                .help("Don't repeat the answers of the last N games"),
+        )
+        .arg(
+            Arg::new("guesses")
+                .multiple_values(true)
+                .value_name("GUESS")
+                .help("Play these guesses, print the board and exit"),
+        )
+        .arg(
+            Arg::new("favorites")
+                .long("favorites")
+                .takes_value(true)
+                .value_name("PATH")
+                .help("Always include the words of this file as possible answers"),
+        )
+        .arg(
+            Arg::new("min-freq")
+                .long("min-freq")
+                .takes_value(true)
+                .value_name("FREQUENCY")
+                .help("Don't accept guesses rarer than FREQUENCY per million, needs a word list with frequencies"),
+        )
+        .arg(
+            Arg::new("alphabet")
+                .long("alphabet")
+                .takes_value(true)
+                .value_name("LETTERS")
+                .help("Only use words made up of the given letters"),
+        )
+        .arg(
+            Arg::new("daily")
+                .long("daily")
+                .takes_value(false)
+                .help("Play the puzzle of the day"),
+        )
+        .arg(
+            Arg::new("daily-date")
+                .long("daily-date")
+                .takes_value(true)
+                .value_name("YYYY-MM-DD")
+                .help("Replay the daily puzzle of a past day"),
+        )
+        .arg(
+            Arg::new("daily-season")
+                .long("daily-season")
+                .value_name("SEASON")
+                .takes_value(true)
+                .requires("daily-puzzle")
+                .help("Pick daily answers from a shuffled schedule without repeats"),
+        )
+        .group(
+            ArgGroup::new("daily-puzzle")
+                .args(&["daily", "daily-date"])
+                .multiple(true),
+        )
+        .arg(
+            Arg::new("zen")
+                .long("zen")
+                .takes_value(false)
+                .help("Unlimited guesses, zen games don't count towards the stats"),
+        )
+        .arg(
+            Arg::new("difficulty")
+                .long("difficulty")
+                .takes_value(false)
+                .help("Show how common the answer is, needs a word list with frequencies"),
+        )
+        .arg(
+            Arg::new("vowels")
+                .long("vowels")
+                .takes_value(false)
+                .help("Tell how many vowels the answer has"),
+        )
+        .arg(
+            Arg::new("concealed")
+                .long("concealed")
+                .takes_value(false)
+                .conflicts_with("zen")
+                .help("Show the feedback only after all guesses are made"),
+        )
+        .arg(
+            Arg::new("hard")
+                .long("hard")
+                .takes_value(false)
+                .help("Guesses have to use all revealed hints"),
+        )
+        .arg(
+            Arg::new("prefix-check")
+                .long("prefix-check")
+                .takes_value(false)
+                .help("Highlight input that can't become a valid word"),
+        )
+        .arg(
+            Arg::new("auto-submit")
+                .long("auto-submit")
+                .takes_value(false)
+                .help("Make the guess as soon as the row is full"),
+        )
+        .arg(
+            Arg::new("serve")
+                .long("serve")
+                .takes_value(true)
+                .value_name("PORT")
+                .help("Host a multiplayer race on the given port"),
+        )
+        .arg(
+            Arg::new("connect")
+                .long("connect")
+                .takes_value(true)
+                .value_name("HOST:PORT")
+                .conflicts_with("serve")
+                .help("Join a multiplayer race"),
+        )
+        .arg(
+            Arg::new("name")
+                .long("name")
+                .takes_value(true)
+                .help("Player name shown to opponents"),
+        )
+        .arg(
+            Arg::new("validate")
+                .long("validate")
+                .takes_value(true)
+                .value_name("PATH")
+                .help("Check a word list for problems and exit"),
+        )
+        .arg(
+            Arg::new("seed")
+                .long("seed")
+                .takes_value(true)
+                .value_name("SEED")
+                .conflicts_with_all(&["daily", "daily-date", "answer", "challenge"])
+                .help("Replay the random game shown as \"seed SEED\" at its end"),
+        )
+        .arg(
+            Arg::new("history")
+                .long("history")
+                .takes_value(true)
+                .value_name("PATH")
+                .help("Append every finished game as a line of JSON to PATH"),
+        )
+        .arg(
+            Arg::new("history-private")
+                .long("history-private")
+                .takes_value(false)
+                .requires("history")
+                .help("Only log the feedback of the guesses, not the words"),
+        )
+        .arg(
+            Arg::new("history-max-kb")
+                .long("history-max-kb")
+                .takes_value(true)
+                .value_name("KB")
+                .requires("history")
+                .help("Move the history to PATH.old once it is larger than KB kilobytes"),
+        )
+        .arg(
+            Arg::new("import-grid")
+                .long("import-grid")
+                .takes_value(false)
+                .help("Analyse an emoji share grid read from stdin and exit"),
+        )
+        .arg(
+            Arg::new("share")
+                .long("share")
+                .takes_value(true)
+                .possible_values(["grid", "streak"])
+                .help("Print an emoji grid of every finished game for sharing"),
+        )
+        .arg(
+            Arg::new("challenge")
+                .long("challenge")
+                .takes_value(true)
+                .value_name("CODE")
+                .conflicts_with_all(&["daily", "daily-date", "answer"])
+                .help("Play the puzzle of a challenge code"),
+        )
+        .arg(
+            Arg::new("benchmark")
+                .long("benchmark")
+                .takes_value(false)
+                .help("Let the solver play every answer and report the hardest words"),
+        )
+        .arg(
+            Arg::new("hardest")
+                .long("hardest")
+                .takes_value(true)
+                .value_name("N")
+                .default_value("10")
+                .help("Number of hardest words listed by --benchmark"),
+        )
+        .arg(
+            Arg::new("stats")
+                .long("stats")
+                .takes_value(false)
+                .help("Show the statistics of your games"),
+        )
+        .arg(
+            Arg::new("length")
+                .long("length")
+                .takes_value(true)
+                .value_name("N")
+                .requires("stats")
+                .help("Only show the statistics of N letter words"),
+        )
+        .arg(
+            Arg::new("duel")
+                .long("duel")
+                .takes_value(false)
+                .help("Two players take turns setting and guessing words"),
+        )
+        .arg(
+            Arg::new("coop")
+                .long("coop")
+                .takes_value(false)
+                .help("Two players take turns guessing on the same board"),
+        )
+        .arg(
+            Arg::new("theme")
+                .long("theme")
+                .takes_value(true)
+                .possible_values(["auto", "dark", "colorblind", "light", "no-color"])
+                .default_value("auto")
+                .help("Color theme, auto detects the terminal background"),
+        )
+        .arg(
+            Arg::new("border")
+                .long("border")
+                .takes_value(true)
+                .possible_values(["ascii", "unicode", "none"])
+                .default_value("ascii")
+                .help("Style of the board grid"),
+        )
+        .arg(
+            Arg::new("no-keyboard")
+                .long("no-keyboard")
+                .takes_value(false)
+                .help("Hide the on-screen keyboard"),
+        )
+        .arg(
+            Arg::new("shuffle-keyboard")
+                .long("shuffle-keyboard")
+                .takes_value(false)
+                .help("Place the keys of the on-screen keyboard in random order"),
+        )
+        .arg(
+            Arg::new("highlight-new")
+                .long("highlight-new")
+                .takes_value(false)
+                .help("Emphasize the keys whose status changed with the last guess"),
+        )
+        .arg(
+            Arg::new("ban-openers")
+                .long("ban-openers")
+                .takes_value(false)
+                .help("Forbid popular openers like crane or slate as the first guess"),
+        )
+        .arg(
+            Arg::new("banned-openers")
+                .long("banned-openers")
+                .takes_value(true)
+                .value_name("PATH")
+                .help("Forbid the words of this file as the first guess"),
+        )
+        .arg(
+            Arg::new("reveal-delay")
+                .long("reveal-delay")
+                .takes_value(true)
+                .value_name("MS|key")
+                .help("Wait MS milliseconds or for a key press before showing the word of a lost game"),
+        )
+        .arg(
+            Arg::new("auto-hint")
+                .long("auto-hint")
+                .takes_value(false)
+                .help("Reveal a correct letter before the last try"),
+        )
+        .arg(
+            Arg::new("mulligan")
+                .long("mulligan")
+                .takes_value(false)
+                .help("Allow taking back one guess per game with Ctrl+Z"),
+        )
+        .arg(
+            Arg::new("forbid-answer-opener")
+                .long("forbid-answer-opener")
+                .takes_value(false)
+                .requires("answer")
+                .help("Reject the answer given by --answer as the first guess"),
+        )
+        .arg(
+            Arg::new("unique-letters")
+                .long("unique-letters")
+                .takes_value(false)
+                .help("Reject guesses which use a letter more than once"),
+        )
+        .arg(
+            Arg::new("anagram")
+                .long("anagram")
+                .takes_value(false)
+                .help("Every guess has to rearrange the letters of the last one"),
+        )
+        .arg(
+            Arg::new("binary")
+                .long("binary")
+                .takes_value(false)
+                .help("Only show whether letters are in the word, not whether they are in place"),
+        )
+        .arg(
+            Arg::new("binary-after-opener")
+                .long("binary-after-opener")
+                .takes_value(false)
+                .conflicts_with("binary")
+                .help("Only the first guess shows whether letters are in place"),
+        )
+        .arg(
+            Arg::new("numbered")
+                .long("numbered")
+                .takes_value(false)
+                .help("Number the rows of the board"),
+        )
+        .arg(
+            Arg::new("animate")
+                .long("animate")
+                .takes_value(false)
+                .help("Reveal the tiles of a guess one after another"),
+        )
+        .arg(
+            Arg::new("random-reveal")
+                .long("random-reveal")
+                .takes_value(false)
+                .requires("animate")
+                .help("Reveal the tiles in a random order"),
+        )
+        .arg(
+            Arg::new("compact")
+                .long("compact")
+                .takes_value(false)
+                .help("Show each guess on a single line"),
+        )
+        .arg(
+            Arg::new("timed")
+                .long("timed")
+                .takes_value(false)
+                .help("Show a clock while playing"),
+        )
+        .arg(
+            Arg::new("turn-limit")
+                .long("turn-limit")
+                .takes_value(true)
+                .value_name("SECONDS")
+                .help("Forfeit the turn if no guess was made in time, implies --timed"),
+        )
+        .arg(
+            Arg::new("invalid-penalty")
+                .long("invalid-penalty")
+                .takes_value(true)
+                .value_name("N")
+                .help("Every N rejected guesses cost a turn"),
+        )
+        .arg(
+            Arg::new("bell")
+                .long("bell")
+                .takes_value(false)
+                .help("Ring the terminal bell on invalid guesses and wins"),
+        )
+        .arg(
+            Arg::new("broadcast")
+                .long("broadcast")
+                .takes_value(true)
+                .value_name("PATH")
+                .help("Write a text copy of the board to a file or pipe for stream overlays"),
+        )
+        .arg(
+            Arg::new("assist")
+                .long("assist")
+                .takes_value(false)
+                .help("Enable analysis helpers"),
+        )
+        .arg(
+            Arg::new("watch")
+                .long("watch")
+                .takes_value(false)
+                .help("Watch the solver play the game, q stops"),
+        )
+        .arg(
+            Arg::new("watch-delay")
+                .long("watch-delay")
+                .takes_value(true)
+                .value_name("MS")
+                .default_value("1000")
+                .help("Time between the guesses of --watch in milliseconds"),
+        )
+        .arg(
+            Arg::new("confidence")
+                .long("confidence")
+                .takes_value(false)
+                .requires("watch")
+                .help("Show how sure the solver is while watching it"),
+        )
+        .arg(
+            Arg::new("solver-log")
+                .long("solver-log")
+                .takes_value(true)
+                .value_name("PATH")
+                .help("Log every decision of the solver in watch and benchmark mode"),
+        )
+        .arg(
+            Arg::new("explain")
+                .long("explain")
+                .takes_value(false)
+                .requires("assist")
+                .help("Show how many candidates each guess eliminated after the game"),
+        )
+        .arg(
+            Arg::new("position-letters")
+                .long("position-letters")
+                .takes_value(false)
+                .help("Show the letters tried at every position after the game"),
+        )
+        .arg(
+            Arg::new("optimal-line")
+                .long("optimal-line")
+                .takes_value(false)
+                .help("Show the guesses the solver would have played after the game"),
+        )
+        .arg(
+            Arg::new("quality")
+                .long("quality")
+                .takes_value(false)
+                .requires("assist")
+                .help("Show how many candidates the typed word would eliminate"),
+        )
+}
+
+fn main() {
+    if let Err(error) = run() {
+        eprintln!("{}", error);
+        std::process::exit(error.exit_code());
+    }
+}
+
+fn run() -> Result<(), RustleError> {
+    let version = version_info();
+    let matches = cli(&version).get_matches();
+
+    let mut stats_path = stats::stats_path();
+    let mut stats = match stats_path.as_deref().map(Stats::load) {
+        Some(Ok(stats)) => stats,
+        Some(Err(error)) => {
+            // keep the file as it is, it may be readable by a newer version
+            eprintln!("Could not load stats, they will not be saved: {}", error);
+            stats_path = None;
+            Stats::default()
+        }
+        None => Stats::default(),
+    };
+
+    let achievements_path = stats::data_dir().map(|dir| dir.join("achievements.json"));
+    let mut achievements = match achievements_path.as_deref().map(Achievements::load) {
+        Some(Ok(achievements)) => achievements,
+        Some(Err(error)) => {
+            eprintln!("Could not load achievements: {}", error);
+            Achievements::default()
+        }
+        None => Achievements::default(),
+    };
+
+    if matches.is_present("stats") {
+        let length = matches
+            .value_of("length")
+            .map(|n| n.parse::<usize>())
+            .transpose()
+            .map_err(|_| RustleError::Usage("Invalid word length".to_string()))?;
+        println!("{}", stats.summary(length));
+        return Ok(());
+    }
+
+    if let Some(path) = matches.value_of("validate") {
+        let validation =
+            words::validate_word_file(path).map_err(|error| RustleError::WordList {
+                list: "word list",
+                error,
+            })?;
+        println!("{}", validation.report(5));
+        if !validation.issues.is_empty() {
+            return Err(RustleError::InvalidWordFile(validation.issues.len()));
+        }
+        return Ok(());
+    }
+
+    if matches.is_present("import-grid") {
+        let grid = io::read_to_string(io::stdin())?;
+        let rows = share::parse_share_grid(&grid)
+            .map_err(|error| RustleError::Usage(error.to_string()))?;
+        print!("{}", share::analysis(&rows));
+        return Ok(());
+    }
+
+    let mode = Mode::from_matches(&matches);
+    let interface = match mode {
+        Mode::Guesses | Mode::Serve | Mode::Benchmark => choose_interface(mode, false, false)?,
+        _ => {
+            let tty = termion::is_tty(&stdin()) && termion::is_tty(&stdout());
+            // raw mode is restored as soon as the probe is dropped
+            let raw_mode = tty && stdout().into_raw_mode().is_ok();
+            choose_interface(mode, tty, raw_mode)?
+        }
+    };
+    let mut config = load_config(interface == Interface::Terminal);
+    let mut game_state = new_game(&matches, &config, &[], true)?;
+
+    if let Some(guesses) = matches.values_of("guesses") {
+        let guesses: Vec<&str> = guesses.collect();
+        print!("{}", play_guesses(&mut game_state, &guesses));
+        return Ok(());
+    }
+
+    if let Some(port) = matches.value_of("serve") {
+        let port = port
+            .parse::<u16>()
+            .map_err(|_| RustleError::Usage(format!("Invalid port: {}", port)))?;
+        server::serve(port, game_state)?;
+        return Ok(());
+    }
+
+    if let Interface::Plain(reason) = interface {
+        eprintln!("{}, playing in plain mode", reason);
+        let stdin = stdin();
+        plain_game(&mut game_state, stdin.lock(), &mut stdout());
+        return Ok(());
+    }
+
+    if let Some(address) = matches.value_of("connect") {
+        let name = matches
+            .value_of("name")
+            .map(|name| name.to_string())
+            .or_else(|| std::env::var("USER").ok())
+            .unwrap_or_else(|| "player".to_string());
+        // names are sent as a single protocol argument
+        let name = name.split_whitespace().collect::<Vec<_>>().join("_");
+        return client::play(address, name, game_state);
+    }
+
+    // only collected when asked for, the benchmark makes many guesses
+    let mut solver_log = matches.value_of("solver-log").map(|_| Vec::new());
+    if matches.is_present("benchmark") {
+        let n = matches
+            .value_of("hardest")
+            .unwrap()
+            .parse::<usize>()
+            .map_err(|_| RustleError::Usage("Invalid number of hardest words".to_string()))?;
+        let results = benchmark::run(
+            &game_state.answers,
+            game_state.max_tries as usize,
+            solver_log.as_mut(),
+        );
+        println!("{}", benchmark::report(&results, n));
+        return write_solver_log(&matches, solver_log);
+    }
+
+    if matches.is_present("watch") {
+        let delay = matches
+            .value_of("watch-delay")
+            .unwrap()
+            .parse::<u64>()
+            .map(Duration::from_millis)
+            .map_err(|_| RustleError::Usage("Invalid delay".to_string()))?;
+        watch::watch(
+            game_state,
+            delay,
+            matches.is_present("confidence"),
+            solver_log.as_mut(),
+        )?;
+        return write_solver_log(&matches, solver_log);
+    }
+
+    if matches.is_present("duel") {
+        return duel::play(game_state);
+    }
+
+    let history_max_bytes = matches
+        .value_of("history-max-kb")
+        .map(|kb| {
+            kb.parse::<u64>()
+                .map(|kb| kb * 1024)
+                .map_err(|_| RustleError::Usage(format!("Invalid history size: {}", kb)))
+        })
+        .transpose()?;
+    let history_private = matches.is_present("history-private");
+    let avoid_recent = matches.value_of("avoid-recent").unwrap();
+    let avoid_recent = avoid_recent.parse::<usize>().map_err(|_| {
+        RustleError::Usage(format!(
+            "Invalid number of recent answers: {}",
+            avoid_recent
+        ))
+    })?;
+    // changed between games with +/-
+    let mut max_tries = game_state.max_tries;
+    let mut session = Session::new();
+    loop {
+        let finished = game_loop(game_state)?;
+        if !finished.is_over() {
+            break;
+        }
+        session.record(&finished);
+        if let Some(message) = session.cold_streak_message(config.cold_streak) {
+            println!("{}", message);
+        }
+        if let Some(path) = matches.value_of("history") {
+            let path = std::path::Path::new(path);
+            if let Err(error) = history::record(path, &finished, history_private, history_max_bytes)
+            {
+                eprintln!("Could not write history: {}", error);
+            }
+        }
+        if finished.theme_changed {
+            config.theme = finished.theme.name.to_string();
+            if let Some(path) = config::config_path() {
+                if let Err(error) = config.save(&path) {
+                    eprintln!("Could not save config: {}", error);
+                }
+            }
+        }
+        if finished.counts_for_stats() {
+            stats.record(&finished);
+            if let Some(path) = &stats_path {
+                if let Err(error) = stats.save(path) {
+                    eprintln!("Could not save stats: {}", error);
+                }
+            }
+        }
+        let unlocked = if finished.counts_for_stats() {
+            achievements.evaluate(&finished, &stats)
+        } else {
+            Vec::new()
+        };
+        for achievement in &unlocked {
+            println!(
+                "Achievement unlocked: {} - {}",
+                achievement.name, achievement.description
+            );
+        }
+        match matches.value_of("share") {
+            Some("grid") => print!("\n{}", share::share_grid(&finished)),
+            Some("streak") => print!("\n{}", share::streak_post(&finished, &stats)),
+            _ => (),
+        }
+        if let (false, Some(path)) = (unlocked.is_empty(), &achievements_path) {
+            if let Err(error) = achievements.save(path) {
+                eprintln!("Could not save achievements: {}", error);
+            }
+        }
+        let screensaver =
+            Some(Duration::from_secs(config.screensaver_seconds)).filter(|idle| !idle.is_zero());
+        game_state = match play_again(
+            !finished.won(),
+            &mut max_tries,
+            screensaver,
+            &finished.theme,
+        )? {
+            NextGame::New => {
+                let mut game_state = new_game(
+                    &matches,
+                    &config,
+                    session.recent_answers(avoid_recent),
+                    false,
+                )?;
+                game_state.max_tries = max_tries;
+                game_state
+            }
+            NextGame::Retry => {
+                let mut game_state = finished;
+                game_state.retry_same_word();
+                game_state.max_tries = max_tries;
+                game_state
+            }
+            NextGame::Quit => break,
+        };
+    }
+    if session.played() > 0 {
+        println!("{}", session.summary());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison, clippy::assertions_on_constants)]
+mod tests {
+    use super::*;
+
+    fn game(answer: &str, words: &[&str]) -> GameState {
+        let words = words.iter().map(|w| w.to_string()).collect();
+        GameState::new(answer.to_string(), words, false)
+    }
+
+    #[test]
+    fn test_new_guess() {
+        let mut game_state =
+            super::GameState::new("hello".to_string(), vec!["hello".to_string()], false);
+        let result = game_state.guess("hello".to_string());
+        assert_eq!(result.unwrap(), true);
+        assert_eq!(game_state.guesses.len(), 1);
+        assert_eq!(game_state.guesses[0], "hello".to_string());
+    }
+
+    #[test]
+    fn test_new_guess_umlaut() {
+        let mut game_state =
+            super::GameState::new("hello".to_string(), vec!["hällö".to_string()], false);
+        let result = game_state.guess("hällö".to_string());
+        assert_eq!(result.unwrap(), false);
+        assert_eq!(game_state.guesses.len(), 1);
+        assert_eq!(game_state.guesses[0], "hällö".to_string());
+    }
+
+    #[test]
+    fn test_new_guess_miss() {
+        let mut game_state = super::GameState::new(
+            "hello".to_string(),
+            vec!["hello".to_string(), "world".to_string()],
+            false,
+        );
+        let result = game_state.guess("world".to_string());
+        assert_eq!(result.unwrap(), false);
+        assert_eq!(game_state.guesses.len(), 1);
+        assert_eq!(game_state.guesses[0], "world".to_string());
+    }
+
+    #[test]
+    fn test_guess_rejects_word_of_wrong_length() {
+        let mut game_state =
+            super::GameState::new("hello".to_string(), vec!["hello".to_string()], false);
+        let result = game_state.guess("hell".to_string());
+        match result {
+            Err(GameError::WrongLength) => assert!(true),
+            _ => assert!(false, "No error raised for wrong length"),
+        }
+        assert_eq!(game_state.guesses.len(), 0);
+    }
+
+    #[test]
+    fn test_guess_rejects_invalid_words() {
+        let mut game_state =
+            super::GameState::new("hello".to_string(), vec!["hello".to_string()], false);
+        let result = game_state.guess("jello".to_string());
+        match result {
+            Err(GameError::InvalidWord) => assert!(true),
+            _ => assert!(false, "No error raised for invalid word"),
+        }
+        assert_eq!(game_state.guesses.len(), 0);
+    }
+
+    #[test]
+    fn test_hard_mode_requires_hints() {
+        let mut game_state = game("hello", &["hello", "jolly", "holly", "world", "cello"]);
+        game_state.hard_mode = true;
+        game_state.guess("jolly".to_string()).unwrap();
+        // the hits "ll" and the found "o" have to be used
+        assert_eq!(
+            game_state.guess("world".to_string()),
+            Err(GameError::MissingHint)
+        );
+        assert!(game_state.guess("holly".to_string()).is_ok());
+        assert!(game_state.uses_hints("hello"));
+        assert!(!game_state.uses_hints("cello"));
+    }
+
+    #[test]
+    fn test_expired_turn_is_forfeited() {
+        let mut game_state = game("hello", &["hello"]);
+        let start = Instant::now();
+        game_state.timer = Some(Timer::new(start, Some(Duration::from_secs(10))));
+        game_state.add_char('h');
+        assert!(!game_state.check_turn_timer(start + Duration::from_secs(5)));
+        assert!(game_state.check_turn_timer(start + Duration::from_secs(10)));
+        assert!(game_state.guesses.is_empty());
+        assert_eq!(game_state.forfeits, vec![0]);
+        assert_eq!(game_state.current_guess, "");
+        assert_eq!(
+            row_letters(&game_state, 0),
+            ("-----".to_string(), vec![HitInfo::Miss; 5])
+        );
+        // the next turn gets the full time again
+        assert!(!game_state.check_turn_timer(start + Duration::from_secs(15)));
+        for i in 2..=6 {
+            assert!(game_state.check_turn_timer(start + Duration::from_secs(10 * i)));
+        }
+        assert!(game_state.is_over());
+        assert!(!game_state.won());
+        assert_eq!(game_state.guess_times, vec![Duration::from_secs(10); 6]);
+        assert!(!game_state.check_turn_timer(start + Duration::from_secs(100)));
+    }
+
+    #[test]
+    fn test_invalid_attempts_cost_a_turn() {
+        let mut game_state = game("hello", &["hello", "jolly"]);
+        let play = |game_state: &mut super::GameState| {
+            for guess in ["xxxxx", "yyyyy", "zzzzz", "jolly", "hello"] {
+                game_state.current_guess = guess.to_string();
+                game_state.confirm();
+            }
+        };
+        play(&mut game_state);
+        assert_eq!(game_state.invalid_attempts, 3);
+        assert_eq!(game_state.guesses.len(), 2);
+        let free_score = history::HistoryEntry::new(&game_state, 0, false).score;
+
+        game_state.retry_same_word();
+        game_state.invalid_penalty = Some(2);
+        play(&mut game_state);
+        assert_eq!(game_state.invalid_attempts, 3);
+        assert_eq!(game_state.guesses, vec!["jolly", "hello"]);
+        assert_eq!(game_state.forfeits, vec![0]);
+        assert_eq!(game_state.row_of_guess(1), 2);
+        assert!(game_state.won());
+        let entry = history::HistoryEntry::new(&game_state, 0, false);
+        assert_eq!(
+            entry.guesses,
+            Some(vec!["jolly".to_string(), "hello".to_string()])
+        );
+        assert_eq!(
+            history::HistoryEntry::new(&game_state, 0, false).score,
+            free_score - 1
+        );
+    }
+
+    #[test]
+    fn test_paused_render_hides_board() {
+        let mut game_state = game("hello", &["hello"]);
+        game_state.timer = Some(Timer::new(Instant::now(), None));
+        game_state.add_char('h');
+        game_state.toggle_pause(Instant::now());
+        let mut buffer = Vec::new();
+        render_game_state(&mut buffer, &game_state);
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(output.contains("PAUSED"));
+        assert!(!output.contains('h'));
+        game_state.toggle_pause(Instant::now());
+        assert!(!game_state.is_paused());
+    }
+
+    #[test]
+    fn test_reveal_absent_letter() {
+        let mut game_state = game("hello", &["hello", "jolly"]);
+        game_state.guess("jolly".to_string()).unwrap();
+        let mut revealed = Vec::new();
+        while let Some(letter) = game_state.reveal_absent_letter() {
+            assert!(!"hello".contains(letter));
+            // misses from guesses and earlier reveals are not revealed again
+            assert_ne!(letter, 'j');
+            assert_ne!(letter, 'y');
+            assert!(!revealed.contains(&letter));
+            revealed.push(letter);
+        }
+        assert_eq!(revealed.len(), 26 - 4 - 2);
+        assert_eq!(game_state.revealed_absent, revealed);
+        assert_eq!(
+            game_state.letter_statuses().get(&revealed[0]),
+            Some(&HitInfo::Miss)
+        );
+    }
+
+    #[test]
+    fn test_zen_games_are_never_lost() {
+        let mut game_state = game("hello", &["hello", "jolly"]);
+        game_state.zen = true;
+        for _ in 0..20 {
+            game_state.guess("jolly".to_string()).unwrap();
+            assert!(!game_state.is_over());
+        }
+        assert_eq!(game_state.visible_rows(), 15..21);
+        assert!(!game_state.counts_for_stats());
+        game_state.guess("hello".to_string()).unwrap();
+        assert!(game_state.is_over());
+        assert!(game_state.won());
+
+        let mut buffer = Vec::new();
+        render_game_state(&mut buffer, &game_state);
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(output.contains("16 earlier guesses"));
+    }
+
+    #[test]
+    fn test_retry_same_word() {
+        let mut game_state = game("hello", &["hello", "jolly"]);
+        for _ in 0..6 {
+            game_state.guess("jolly".to_string()).unwrap();
+        }
+        game_state.add_char('x');
+        game_state.set_last_error(GameError::InvalidWord);
+        assert!(game_state.is_over());
+        game_state.retry_same_word();
+        assert_eq!(game_state.word, "hello");
+        assert_eq!(game_state.valid_words, vec!["hello", "jolly"]);
+        assert!(game_state.guesses.is_empty());
+        assert_eq!(game_state.current_guess, "");
+        assert_eq!(game_state.last_error, None);
+        assert!(!game_state.is_over());
+        assert!(!game_state.counts_for_stats());
+    }
+
+    #[test]
+    fn test_get_guess_hits() {
+        let mut game_state = super::GameState::new(
+            "hello".to_string(),
+            vec!["hello".to_string(), "jolly".to_string()],
+            false,
+        );
+        let result = game_state.guess("jolly".to_string());
+        assert_eq!(result.unwrap(), false);
+        let hits = game_state.get_guess_hits(0);
+        assert_eq!(hits.len(), 5);
+        assert_eq!(hits[0], HitInfo::Miss);
+        assert_eq!(hits[1], HitInfo::Contains);
+        assert_eq!(hits[2], HitInfo::Hit);
+        assert_eq!(hits[3], HitInfo::Hit);
+        assert_eq!(hits[4], HitInfo::Miss);
+    }
+
+    #[test]
+    fn test_compute_hits() {
+        let hits = GameState::compute_hits("hello", "jolly");
+        assert_eq!(hits.len(), 5);
+        assert_eq!(hits[0], HitInfo::Miss);
+        assert_eq!(hits[1], HitInfo::Contains);
+        assert_eq!(hits[2], HitInfo::Hit);
+        assert_eq!(hits[3], HitInfo::Hit);
+        assert_eq!(hits[4], HitInfo::Miss);
+    }
+
+    #[test]
+    fn test_compute_hits_does_not_touch_guesses() {
+        let game_state = game("hello", &[]);
+        assert_eq!(
+            GameState::compute_hits(&game_state.word, "hello"),
+            vec![HitInfo::Hit; 5]
+        );
+        assert!(game_state.guesses.is_empty());
+        // longer guesses than the answer don't panic
+        assert_eq!(
+            GameState::compute_hits("hi", "hih"),
+            vec![HitInfo::Hit, HitInfo::Hit, HitInfo::Contains]
+        );
+    }
+
+    #[test]
+    fn test_add_char() {
+        let mut game_state =
+            super::GameState::new("hello".to_string(), vec!["hello".to_string()], false);
+        game_state.add_char('h');
+        assert_eq!(game_state.current_guess, "h".to_string());
+        game_state.add_char('e');
+        assert_eq!(game_state.current_guess, "he".to_string());
+        game_state.add_char('l');
+        assert_eq!(game_state.current_guess, "hel".to_string());
+        game_state.add_char('l');
+        assert_eq!(game_state.current_guess, "hell".to_string());
+        game_state.add_char('o');
+        assert_eq!(game_state.current_guess, "hello".to_string());
+        game_state.add_char('o');
+        assert_eq!(game_state.current_guess, "hello".to_string());
+    }
+
+    #[test]
+    fn test_auto_submit_on_full_row() {
+        let mut game_state = game("hello", &["hello", "jolly"]);
+        game_state.auto_submit = true;
+        for c in "joll".chars() {
+            game_state.add_char(c);
+        }
+        assert!(game_state.guesses.is_empty());
+        assert_eq!(game_state.last_error, None);
+        game_state.add_char('y');
+        assert_eq!(game_state.guesses, vec!["jolly".to_string()]);
+        assert_eq!(game_state.current_guess, "");
+
+        // an invalid word is rejected like on Enter, further keys don't retry it
+        for c in "hellx".chars() {
+            game_state.add_char(c);
+        }
+        assert_eq!(game_state.last_error, Some(super::GameError::InvalidWord));
+        assert_eq!(game_state.guesses.len(), 1);
+
+        // once the game is won, typing neither fills nor submits another row
+        game_state.current_guess.clear();
+        for c in "hell".chars() {
+            assert!(!game_state.add_char(c));
+        }
+        assert!(game_state.add_char('o'));
+        assert!(game_state.won());
+        for c in "jolly".chars() {
+            assert!(!game_state.add_char(c));
+        }
+        assert_eq!(game_state.guesses, vec!["jolly", "hello"]);
+        assert_eq!(game_state.current_guess, "");
+        assert_eq!(
+            game_state.guess("jolly".to_string()),
+            Err(super::GameError::GameOver)
+        );
+    }
+
+    #[test]
+    fn test_add_char_test_umlaut() {
+        let mut game_state =
+            super::GameState::new("hello".to_string(), vec!["hello".to_string()], false);
+        game_state.add_char('Ü');
+        assert_eq!(game_state.current_guess, "ü".to_string());
+    }
+
+    #[test]
+    fn test_add_char_test_umlaut_length() {
+        let mut game_state =
+            super::GameState::new("hello".to_string(), vec!["hello".to_string()], false);
+        game_state.add_char('Ü');
+        game_state.add_char('Ü');
+        game_state.add_char('Ü');
+        game_state.add_char('Ü');
+        game_state.add_char('Ü');
+        assert_eq!(game_state.current_guess, "üüüüü".to_string());
+    }
+
+    #[test]
+    fn test_add_char_converts_to_lowercase() {
+        let mut game_state =
+            super::GameState::new("hello".to_string(), vec!["hello".to_string()], false);
+        game_state.add_char('H');
+        assert_eq!(game_state.current_guess, "h".to_string());
+        game_state.add_char('E');
+        assert_eq!(game_state.current_guess, "he".to_string());
+    }
+
+    #[test]
+    fn test_back() {
+        let mut game_state =
+            super::GameState::new("hello".to_string(), vec!["hello".to_string()], false);
+        game_state.add_char('h');
+        assert_eq!(game_state.current_guess, "h".to_string());
+        game_state.add_char('e');
+        assert_eq!(game_state.current_guess, "he".to_string());
+        game_state.add_char('l');
+        assert_eq!(game_state.current_guess, "hel".to_string());
+        game_state.back();
+        assert_eq!(game_state.current_guess, "he".to_string());
+    }
+
+    #[test]
+    fn test_cofirm_with_too_few_chars() {
+        let mut game_state =
+            super::GameState::new("hello".to_string(), vec!["hello".to_string()], false);
+        game_state.add_char('h');
+        game_state.add_char('e');
+        game_state.confirm();
+        assert_eq!(game_state.last_error.unwrap(), GameError::WrongLength);
+        assert_eq!(game_state.current_guess.len(), 0);
+        assert_eq!(game_state.guesses.len(), 0);
+    }
+
+    #[test]
+    fn test_cofirm_with_invalid_word() {
+        let mut game_state =
+            super::GameState::new("hello".to_string(), vec!["hello".to_string()], false);
+        game_state.add_char('j');
+        game_state.add_char('e');
+        game_state.add_char('l');
+        game_state.add_char('l');
+        game_state.add_char('o');
+        game_state.confirm();
+        assert_eq!(game_state.last_error.unwrap(), GameError::InvalidWord);
+        assert_eq!(game_state.current_guess.len(), 0);
+        assert_eq!(game_state.guesses.len(), 0);
+    }
+
+    #[test]
+    fn test_cofirm() {
+        let mut game_state =
+            super::GameState::new("hello".to_string(), vec!["hello".to_string()], false);
+        game_state.add_char('h');
+        // produce error
+        game_state.confirm();
+        game_state.add_char('h');
+        game_state.add_char('e');
+        game_state.add_char('l');
+        game_state.add_char('l');
+        game_state.add_char('o');
+        game_state.confirm();
+        assert_eq!(game_state.last_error, None);
+        assert_eq!(game_state.current_guess.len(), 0);
+        assert_eq!(game_state.guesses.len(), 1);
+    }
+
+    #[test]
+    fn test_accepts_any_word() {
         let mut game_state = super::GameState::new(
             "hello".to_string(),
             vec!["hello".to_string(), "jolly".to_string()],
-            false,
+            true,
+        );
+        let result = game_state.guess("milli".to_string()).unwrap();
+        assert_eq!(result, false);
+    }
+
+    #[test]
+    fn test_overlap_hits_of_other_lengths() {
+        use HitInfo::*;
+        assert_eq!(
+            GameState::compute_overlap_hits("hello", "help"),
+            vec![Hit, Hit, Hit, Miss]
+        );
+        assert_eq!(
+            GameState::compute_overlap_hits("hello", "yellows"),
+            vec![Miss, Hit, Hit, Hit, Hit, None, None]
+        );
+        assert_eq!(
+            GameState::compute_overlap_hits("hello", "ole"),
+            vec![Contains, Contains, Contains]
+        );
+        assert_eq!(
+            GameState::compute_overlap_hits("hello", "hello"),
+            GameState::compute_hits("hello", "hello")
+        );
+    }
+
+    #[test]
+    fn test_overlap_accepts_other_lengths() {
+        let mut game_state = game("hello", &[]);
+        game_state.any_word = true;
+        assert_eq!(
+            game_state.guess("help".to_string()),
+            Err(GameError::WrongLength)
+        );
+        game_state.overlap = true;
+        assert_eq!(game_state.guess("help".to_string()), Ok(false));
+        assert_eq!(game_state.guess(String::new()), Err(GameError::WrongLength));
+        assert_eq!(game_state.guess("lehlox".to_string()), Ok(false));
+        assert_eq!(
+            game_state.visible_hits(1),
+            vec![
+                HitInfo::Contains,
+                HitInfo::Hit,
+                HitInfo::Contains,
+                HitInfo::Hit,
+                HitInfo::Hit,
+                HitInfo::None
+            ]
+        );
+        // the "x" past the end of the answer isn't known to be absent
+        assert_eq!(game_state.letter_statuses().get(&'x'), Some(&HitInfo::None));
+        assert_eq!(game_state.guess("hello".to_string()), Ok(true));
+    }
+
+    #[test]
+    fn test_rendering_short_overlap_guess() {
+        let mut game_state = game("hello", &[]);
+        game_state.any_word = true;
+        game_state.overlap = true;
+        for c in "hel".chars() {
+            game_state.add_char(c);
+        }
+        game_state.confirm();
+        assert_eq!(game_state.guesses, vec!["hel"]);
+        let mut buffer = Vec::new();
+        render_game_state(&mut buffer, &game_state);
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(output.contains('l'));
+    }
+
+    #[test]
+    fn test_attempted_letters_per_position() {
+        let mut game_state = game("hello", &[]);
+        game_state.any_word = true;
+        for guess in ["crane", "shell", "hello"] {
+            game_state.guess(guess.to_string()).unwrap();
+        }
+        game_state.forfeit_turn(Instant::now());
+        assert_eq!(
+            game_state.attempted_letters(),
+            vec![
+                vec!['c', 'h', 's'],
+                vec!['e', 'h', 'r'],
+                vec!['a', 'e', 'l'],
+                vec!['l', 'n'],
+                vec!['e', 'l', 'o'],
+            ]
+        );
+        assert!(position_letters_table(&game_state).contains("4 L | L N\n"));
+    }
+
+    #[test]
+    fn test_has_valid_prefix() {
+        let game_state = game("hello", &["hello", "jolly", "help"]);
+        assert!(game_state.has_valid_prefix(""));
+        assert!(game_state.has_valid_prefix("hel"));
+        assert!(game_state.has_valid_prefix("jolly"));
+        assert!(!game_state.has_valid_prefix("hex"));
+        // "help" is too short to be a valid guess
+        assert!(!game_state.has_valid_prefix("help"));
+    }
+
+    #[test]
+    fn test_remaining_candidate_count_decreases() {
+        let mut game_state = game(
+            "hello",
+            &["hello", "jolly", "hells", "world", "belly", "cello"],
+        );
+        let mut count = game_state.remaining_candidate_count();
+        assert_eq!(count, 6);
+        for guess in ["belly", "world", "hells"] {
+            game_state.guess(guess.to_string()).unwrap();
+            let new_count = game_state.remaining_candidate_count();
+            assert!(new_count < count, "{} did not reduce candidates", guess);
+            count = new_count;
+        }
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_reveal_remaining_counts_uses() {
+        let mut game_state = game("hello", &["hello"]);
+        game_state.reveal_remaining();
+        game_state.reveal_remaining();
+        assert_eq!(game_state.remaining_reveals, 1);
+        assert!(game_state.show_remaining);
+    }
+
+    #[test]
+    fn test_is_over() {
+        let mut game_state = game("hello", &["hello", "jolly"]);
+        game_state.max_tries = 2;
+        game_state.guess("jolly".to_string()).unwrap();
+        assert!(!game_state.is_over());
+        game_state.guess("jolly".to_string()).unwrap();
+        assert!(game_state.is_over());
+    }
+
+    #[test]
+    fn test_remote_game() {
+        let mut game_state = super::GameState::remote(5, 6);
+        game_state.add_remote_guess(
+            "jolly".to_string(),
+            vec![
+                HitInfo::Miss,
+                HitInfo::Contains,
+                HitInfo::Hit,
+                HitInfo::Hit,
+                HitInfo::Miss,
+            ],
+        );
+        assert!(!game_state.won());
+        assert_eq!(game_state.get_guess_hits(0)[1], HitInfo::Contains);
+        game_state.add_remote_guess("hello".to_string(), vec![HitInfo::Hit; 5]);
+        assert!(game_state.won());
+        assert!(game_state.is_over());
+    }
+
+    #[test]
+    fn test_turns_alternate() {
+        let mut game_state = game("hello", &["hello", "jolly"]);
+        game_state.players = 2;
+        assert_eq!(game_state.current_player(), 0);
+        game_state.guess("jolly".to_string()).unwrap();
+        assert_eq!(game_state.current_player(), 1);
+        game_state.guess("jolly".to_string()).unwrap();
+        assert_eq!(game_state.current_player(), 0);
+        game_state.guess("jolly".to_string()).unwrap();
+        assert_eq!(game_state.current_player(), 1);
+        assert_eq!(game_state.solving_player(), None);
+        game_state.guess("hello".to_string()).unwrap();
+        assert_eq!(game_state.solving_player(), Some(1));
+    }
+
+    #[test]
+    fn test_single_player_always_has_the_turn() {
+        let mut game_state = game("hello", &["hello", "jolly"]);
+        game_state.guess("jolly".to_string()).unwrap();
+        assert_eq!(game_state.current_player(), 0);
+        game_state.guess("hello".to_string()).unwrap();
+        assert_eq!(game_state.solving_player(), Some(0));
+    }
+
+    #[test]
+    fn test_rendering_miss_sets_foreground() {
+        let mut game_state = game("hello", &["hello", "jumpy"]);
+        game_state.guess("jumpy".to_string()).unwrap();
+        let mut buffer = Vec::new();
+        render_game_state(&mut buffer, &game_state);
+        let output = String::from_utf8(buffer).unwrap();
+        let miss = &game_state.theme.miss;
+        assert!(output.contains(&format!("{}{}j", miss.bg, miss.fg)));
+        assert!(output.contains(&format!("{}{}y", miss.bg, miss.fg)));
+    }
+
+    #[test]
+    fn test_compact_rendering_has_one_line_per_guess() {
+        let mut game_state = game("hello", &["hello", "jolly", "world"]);
+        game_state.compact = true;
+        game_state.guess("jolly".to_string()).unwrap();
+        game_state.guess("world".to_string()).unwrap();
+        let mut buffer = Vec::new();
+        render_game_state(&mut buffer, &game_state);
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(!output.contains('|'));
+        let lines: Vec<&str> = output.trim_end_matches("\r\n").split("\r\n").collect();
+        // two guesses and the input line
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].contains(" j ") && lines[0].contains(" y "));
+        assert!(lines[1].contains(" w ") && lines[1].contains(" d "));
+        assert!(lines[2].contains(" _ "));
+    }
+
+    #[test]
+    fn test_version_info() {
+        let version = version_info();
+        assert!(version.starts_with(&format!("{} (", env!("CARGO_PKG_VERSION"))));
+        assert!(version.ends_with(&format!("({} words)", words::embedded_word_count())));
+    }
+
+    #[test]
+    fn test_preview_uses_remaining_candidates() {
+        let mut game_state = game("hello", &["hello", "jolly", "cello", "belly", "world"]);
+        game_state.guess("world".to_string()).unwrap();
+        // only hello and cello are left
+        let groups = game_state.preview("cello");
+        assert_eq!(groups.len(), 2);
+        assert!(groups.iter().all(|(_, count)| *count == 1));
+        let groups = game_state.preview("jolly");
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].1, 2);
+    }
+
+    #[test]
+    fn test_preview_requires_assist() {
+        let mut game_state = game("hello", &["hello"]);
+        game_state.toggle_preview();
+        assert!(!game_state.show_preview);
+        game_state.assist = true;
+        game_state.toggle_preview();
+        assert!(game_state.show_preview);
+    }
+
+    #[test]
+    fn test_guess_quality_needs_full_input() {
+        let mut game_state = game("hello", &["hello", "jolly", "holly", "world"]);
+        game_state.quality = true;
+        game_state.current_guess = "holly".to_string();
+        assert_eq!(game_state.guess_quality(), None);
+        game_state.assist = true;
+        assert_eq!(game_state.guess_quality(), Some(0.75));
+        game_state.current_guess = "holl".to_string();
+        assert_eq!(game_state.guess_quality(), None);
+        assert_eq!(quality_meter(0.75), "[########..]");
+    }
+
+    #[test]
+    fn test_rendering_border_styles() {
+        let mut game_state = game("hello", &["hello"]);
+        let render = |game_state: &GameState| {
+            let mut buffer = Vec::new();
+            render_game_state(&mut buffer, game_state);
+            String::from_utf8(buffer).unwrap()
+        };
+
+        let output = render(&game_state);
+        assert!(output.contains("-----------"));
+        assert!(output.contains('|'));
+
+        game_state.border = BorderStyle::unicode();
+        let output = render(&game_state);
+        assert!(output.contains("┌─┬─┬─┬─┬─┐"));
+        assert!(output.contains("└─┴─┴─┴─┴─┘"));
+        assert!(output.contains('│'));
+        assert!(!output.contains('|'));
+
+        game_state.border = BorderStyle::none();
+        let output = render(&game_state);
+        assert!(!output.contains('-'));
+        assert!(!output.contains('|'));
+        assert!(!output.contains('─'));
+    }
+
+    #[test]
+    fn test_letter_statuses() {
+        let mut game_state = game("hello", &["hello", "jolly", "olive"]);
+        game_state.guess("olive".to_string()).unwrap();
+        game_state.guess("jolly".to_string()).unwrap();
+        let statuses = game_state.letter_statuses();
+        assert_eq!(statuses[&'j'], HitInfo::Miss);
+        assert_eq!(statuses[&'e'], HitInfo::Contains);
+        // contained in the first guess, a hit in the second
+        assert_eq!(statuses[&'l'], HitInfo::Hit);
+        assert_eq!(statuses.get(&'z'), None);
+    }
+
+    #[test]
+    fn test_status_diff_between_guesses() {
+        let mut game_state = game("hello", &["hello", "jolly", "olive"]);
+        game_state.highlight_new = true;
+        game_state.current_guess = "olive".to_string();
+        game_state.confirm();
+        assert_eq!(game_state.new_statuses, game_state.letter_statuses());
+
+        game_state.current_guess = "jolly".to_string();
+        game_state.confirm();
+        // l moved into place, j and y are new, o is still misplaced
+        let expected: HashMap<char, HitInfo> = [
+            ('j', HitInfo::Miss),
+            ('l', HitInfo::Hit),
+            ('y', HitInfo::Miss),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(game_state.new_statuses, expected);
+        assert!(super::GameState::status_diff(&expected, &expected).is_empty());
+    }
+
+    #[test]
+    fn test_rendering_keyboard() {
+        let mut game_state = game("hello", &["hello", "jumpy"]);
+        game_state.guess("jumpy".to_string()).unwrap();
+        let mut buffer = Vec::new();
+        render_game_state(&mut buffer, &game_state);
+        let output = String::from_utf8(buffer).unwrap();
+        let miss = &game_state.theme.miss;
+        assert!(output.contains(&format!("{}{}u", miss.bg, miss.fg)));
+        assert!(output.contains("q"));
+    }
+
+    #[test]
+    fn test_rendering_custom_keyboard_layout() {
+        let mut game_state = game("größe", &["größe", "grübe"]);
+        game_state.guess("grübe".to_string()).unwrap();
+        let keyboard = Keyboard::from_rows(&["grüöß".to_string(), "be".to_string()]);
+        let mut buffer = Vec::new();
+        render_keyboard(&mut buffer, &game_state, &keyboard, 10, 20);
+
+        let theme = &game_state.theme;
+        let key = |c: char, status: HitInfo| {
+            format!("{}{}{} ", theme.style(&status), c, termion::style::Reset)
+        };
+        let expected = [
+            termion::cursor::Goto(10, 20).to_string(),
+            key('g', HitInfo::Hit),
+            key('r', HitInfo::Hit),
+            key('ü', HitInfo::Miss),
+            key('ö', HitInfo::None),
+            key('ß', HitInfo::None),
+            termion::cursor::Goto(11, 21).to_string(),
+            key('b', HitInfo::Miss),
+            key('e', HitInfo::Hit),
+        ]
+        .concat();
+        assert_eq!(String::from_utf8(buffer).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_rendering_error_in_theme_color() {
+        let mut game_state = game("hello", &["hello"]);
+        game_state.add_char('h');
+        game_state.confirm();
+        assert_eq!(game_state.last_error, Some(GameError::WrongLength));
+        for compact in [false, true] {
+            game_state.compact = compact;
+            let mut buffer = Vec::new();
+            render_game_state(&mut buffer, &game_state);
+            let output = String::from_utf8(buffer).unwrap();
+            let expected = format!("{}{}", game_state.theme.error, GameError::WrongLength);
+            assert!(output.contains(&expected));
+        }
+        game_state.add_char('h');
+        assert_eq!(game_state.last_error, None);
+    }
+
+    #[test]
+    fn test_rendering_invalid_prefix_in_theme_color() {
+        let mut game_state = game("hello", &["hello"]);
+        game_state.prefix_check = true;
+        game_state.theme = Theme::from_name("no-color").unwrap();
+        game_state.add_char('x');
+        let mut buffer = Vec::new();
+        render_game_state(&mut buffer, &game_state);
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(output.contains(&format!("{}x", game_state.theme.error)));
+        let red = termion::color::Fg(termion::color::Red).to_string();
+        assert!(!output.contains(&red));
+    }
+
+    #[test]
+    fn test_concealed_feedback_is_revealed_at_the_end() {
+        let mut game_state = game("hello", &["hello", "jolly"]);
+        game_state.concealed = true;
+        game_state.guess("jolly".to_string()).unwrap();
+        game_state.guess("hello".to_string()).unwrap();
+        assert!(!game_state.is_over());
+        let theme = game_state.theme.clone();
+        let render = |game_state: &GameState| {
+            let mut buffer = Vec::new();
+            render_game_state(&mut buffer, game_state);
+            String::from_utf8(buffer).unwrap()
+        };
+        let output = render(&game_state);
+        for style in [&theme.hit, &theme.contains, &theme.miss] {
+            assert!(!output.contains(&style.to_string()));
+        }
+
+        for _ in 0..4 {
+            game_state.guess("jolly".to_string()).unwrap();
+        }
+        assert!(game_state.is_over());
+        assert!(game_state.won());
+        let output = render(&game_state);
+        assert!(output.contains(&format!("{}h", theme.hit)));
+    }
+
+    #[test]
+    fn test_bell_on_invalid_guess() {
+        let mut game_state = game("hello", &["hello"]);
+        game_state.add_char('h');
+        game_state.confirm();
+        let mut buffer = Vec::new();
+        ring_bell(&mut buffer, &game_state);
+        assert!(buffer.is_empty());
+        game_state.bell = true;
+        ring_bell(&mut buffer, &game_state);
+        assert_eq!(buffer, b"\x07");
+    }
+
+    #[test]
+    fn test_board_fits() {
+        assert_eq!(board_width(5), 20);
+        assert!(board_fits(5, 80));
+        assert!(board_fits(5, 20));
+        assert!(!board_fits(5, 19));
+        assert!(board_fits(30, 80));
+        assert!(!board_fits(40, 80));
+        assert!(!board_fits(12, 24));
+    }
+
+    #[test]
+    fn test_rendering_keyboard_legend() {
+        let mut game_state = game("hello", &["hello"]);
+        let mut buffer = Vec::new();
+        render_game_state(&mut buffer, &game_state);
+        assert!(!String::from_utf8(buffer).unwrap().contains("correct"));
+
+        game_state.keyboard_legend = true;
+        let mut buffer = Vec::new();
+        render_game_state(&mut buffer, &game_state);
+        let output = String::from_utf8(buffer).unwrap();
+        let hit = &game_state.theme.hit;
+        assert!(output.contains(&format!("{}{}   ", hit.bg, hit.fg)));
+        assert!(output.contains("correct"));
+        assert!(output.contains("present"));
+        assert!(output.contains("absent"));
+    }
+
+    #[test]
+    fn test_available_letters() {
+        let mut game_state = game("hello", &["hello", "world", "humor"]);
+        assert!(game_state.available_letters().is_empty());
+        game_state.guesses.push("world".to_string());
+        game_state.guesses.push("humor".to_string());
+        let statuses = game_state.letter_statuses();
+        let mut known: Vec<char> = "worldhumor"
+            .chars()
+            .filter(|c| "hello".contains(*c))
+            .collect();
+        known.sort_unstable();
+        known.dedup();
+        assert_eq!(game_state.available_letters(), known);
+        assert_eq!(known, vec!['h', 'l', 'o']);
+        assert_eq!(statuses[&'o'], HitInfo::Contains);
+    }
+
+    #[test]
+    fn test_eliminated_letters_exclude_present_ones() {
+        let mut game_state = game("world", &["world", "hello", "lurid"]);
+        game_state.guesses.push("hello".to_string());
+        // l and o were found, the rest of "hello" is eliminated
+        assert_eq!(game_state.eliminated_letters(), vec!['e', 'h']);
+        game_state.guesses.push("lurid".to_string());
+        assert_eq!(game_state.eliminated_letters(), vec!['e', 'h', 'i', 'u']);
+
+        game_state.eliminated_letters = true;
+        let mut buffer = Vec::new();
+        render_game_state(&mut buffer, &game_state);
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(output.contains(&format!(
+            "Eliminated: {}e{}",
+            game_state.theme.miss,
+            termion::style::Reset
+        )));
+    }
+
+    #[test]
+    fn test_tries_pips() {
+        let mut game_state = game("hello", &["hello", "jolly"]);
+        game_state.guesses = vec!["jolly".to_string(), "jolly".to_string()];
+        game_state.tries_pips = true;
+        let mut buffer = Vec::new();
+        render_game_state(&mut buffer, &game_state);
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(output.contains("Tries: \u{25cf}\u{25cf}\u{25cb}\u{25cb}\u{25cb}\u{25cb}"));
+
+        game_state.max_tries = 4;
+        game_state.compact = true;
+        let mut buffer = Vec::new();
+        render_game_state(&mut buffer, &game_state);
+        let output = String::from_utf8(buffer).unwrap();
+        assert_eq!(output.matches('\u{25cf}').count(), 2);
+        assert_eq!(output.matches('\u{25cb}').count(), 2);
+
+        game_state.zen = true;
+        assert_eq!(tries_pips(&game_state), None);
+    }
+
+    #[test]
+    fn test_debug_answer_needs_env_var() {
+        let mut game_state = game("hello", &["hello"]);
+        game_state.debug = debug_enabled(None);
+        assert_eq!(game_state.debug_answer(), None);
+        game_state.debug = debug_enabled(Some("0"));
+        assert_eq!(game_state.debug_answer(), None);
+        game_state.debug = debug_enabled(Some("1"));
+        assert_eq!(game_state.debug_answer(), Some("hello"));
+        assert!(game_state.guesses.is_empty());
+    }
+
+    #[test]
+    fn test_explanation_matches_filtering() {
+        let answers: Vec<String> = ["hello", "jolly", "world", "hells", "belly", "cello"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let mut game_state = super::GameState::new("hello".to_string(), answers.clone(), false);
+        game_state.guesses = vec!["jolly".to_string(), "belly".to_string()];
+        let rows = game_state.explanation();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].before, answers.len());
+        let mut candidates = answers.clone();
+        for (i, row) in rows.iter().enumerate() {
+            assert!(row.after <= row.before);
+            assert_eq!(row.before, candidates.len());
+            candidates = solver::filter_candidates(
+                &candidates,
+                &row.guess,
+                &GameState::compute_hits("hello", &row.guess),
+            );
+            assert_eq!(row.after, candidates.len());
+            if i > 0 {
+                assert_eq!(row.before, rows[i - 1].after);
+            }
+        }
+        assert!(explanation_table(&rows).contains("Eliminated"));
+    }
+
+    #[test]
+    fn test_daily_word_override() {
+        let mut game_state = game("hello", &["hello", "world", "cello"]);
+        apply_daily_word(&mut game_state, Some("World"));
+        assert_eq!(game_state.word, "world");
+        apply_daily_word(&mut game_state, Some("house"));
+        apply_daily_word(&mut game_state, Some("cellos"));
+        apply_daily_word(&mut game_state, None);
+        assert_eq!(game_state.word, "world");
+    }
+
+    #[test]
+    fn test_random_reveal_order_is_permutation() {
+        let mut game_state = game("hello", &["hello"]);
+        game_state.guesses = vec!["world".to_string(), "hello".to_string()];
+        assert_eq!(game_state.reveal_order(0), vec![0, 1, 2, 3, 4]);
+        game_state.random_reveal = true;
+        for i in 0..2 {
+            let mut order = game_state.reveal_order(i);
+            assert_eq!(order, game_state.reveal_order(i));
+            order.sort_unstable();
+            assert_eq!(order, vec![0, 1, 2, 3, 4]);
+        }
+        game_state.revealing = Some(vec![3]);
+        assert_eq!(game_state.shown_hits(0), game_state.get_guess_hits(0));
+        let mut hidden = vec![HitInfo::None; 5];
+        hidden[3] = HitInfo::Hit;
+        assert_eq!(game_state.shown_hits(1), hidden);
+    }
+
+    #[test]
+    fn test_play_guesses() {
+        let mut game_state = game("hello", &["hello", "world", "jolly"]);
+        let output = play_guesses(
+            &mut game_state,
+            &["world", "jolly", "xyzzy", "hello", "world"],
+        );
+        assert_eq!(game_state.guesses, vec!["world", "jolly", "hello"]);
+        assert!(game_state.won());
+        assert_eq!(
+            output,
+            "xyzzy: Word is not valid\n\
+             world: the game is already over\n\
+             rustle 3/6 won\n\
+             world .Y.G.\n\
+             jolly .YGG.\n\
+             hello GGGGG\n"
+        );
+    }
+
+    #[test]
+    fn test_closest_guess() {
+        let mut game_state = game(
+            "hello",
+            &["hello", "world", "jolly", "cello", "belly", "fuzzy"],
+        );
+        assert_eq!(game_state.closest_guess(), None);
+        game_state.guesses = vec!["fuzzy".to_string()];
+        assert_eq!(game_state.closest_guess(), None);
+        game_state.guesses = ["world", "jolly", "cello", "belly", "fuzzy", "world"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        // cello and belly both have four hits, cello came first
+        assert_eq!(game_state.closest_guess(), Some(2));
+        let mut buffer = Vec::new();
+        render_game_state(&mut buffer, &game_state);
+        assert_eq!(
+            String::from_utf8(buffer)
+                .unwrap()
+                .matches("< closest")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_render_writes_frame_at_once() {
+        /// Records every write separately.
+        struct Writes(Vec<Vec<u8>>);
+        impl Write for Writes {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.push(buf.to_vec());
+                Ok(buf.len())
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+        let mut game_state = game("hello", &["hello", "jolly"]);
+        game_state.guesses = vec!["jolly".to_string()];
+        game_state.current_guess = "hel".to_string();
+
+        let mut incremental = Writes(Vec::new());
+        render_game_state(&mut incremental, &game_state);
+        assert!(incremental.0.len() > 1);
+        let mut batched = Writes(Vec::new());
+        render(&mut batched, &game_state);
+        assert_eq!(batched.0.len(), 1);
+        assert_eq!(batched.0.concat(), incremental.0.concat());
+    }
+
+    #[test]
+    fn test_rendering_numbered_rows() {
+        let mut game_state = game("hello", &["hello"]);
+        let mut buffer = Vec::new();
+        render_game_state(&mut buffer, &game_state);
+        let number = |row: u16| format!("{}{:>2}", termion::cursor::Goto(7, 4 + row * 2), row + 1);
+        assert!(!String::from_utf8(buffer).unwrap().contains(&number(0)));
+
+        game_state.numbered = true;
+        let mut buffer = Vec::new();
+        render_game_state(&mut buffer, &game_state);
+        let output = String::from_utf8(buffer).unwrap();
+        let positions: Vec<usize> = (0..6)
+            .map(|row| output.find(&number(row)).unwrap())
+            .collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_binary_feedback() {
+        let mut game_state = game("hello", &["hello", "jolly", "olleh", "lolly"]);
+        game_state.feedback = FeedbackStrategy::Binary;
+        game_state.guesses = vec!["jolly".to_string()];
+        assert_eq!(game_state.get_guess_hits(0)[2], HitInfo::Hit);
+        assert_eq!(game_state.shown_hits(0)[2], HitInfo::Contains);
+        assert_eq!(game_state.letter_statuses()[&'l'], HitInfo::Contains);
+        // olleh would show the same colors as hello when only presence is shown
+        assert_eq!(
+            game_state.remaining_candidates(),
+            vec!["hello".to_string(), "olleh".to_string()]
         );
-        let result = game_state.guess("jolly".to_string());
-        assert_eq!(result.unwrap(), false);
-        let hits = game_state.get_guess_hits(0);
-        assert_eq!(hits.len(), 5);
-        assert_eq!(hits[0], HitInfo::Miss);
-        assert_eq!(hits[1], HitInfo::Contains);
-        assert_eq!(hits[2], HitInfo::Hit);
-        assert_eq!(hits[3], HitInfo::Hit);
-        assert_eq!(hits[4], HitInfo::Miss);
     }
 
     #[test]
-    fn test_add_char() {
-        let mut game_state =
-            super::GameState::new("hello".to_string(), vec!["hello".to_string()], false);
-        game_state.add_char('h');
-        assert_eq!(game_state.current_guess, "h".to_string());
-        game_state.add_char('e');
-        assert_eq!(game_state.current_guess, "he".to_string());
-        game_state.add_char('l');
-        assert_eq!(game_state.current_guess, "hel".to_string());
-        game_state.add_char('l');
-        assert_eq!(game_state.current_guess, "hell".to_string());
-        game_state.add_char('o');
-        assert_eq!(game_state.current_guess, "hello".to_string());
-        game_state.add_char('o');
-        assert_eq!(game_state.current_guess, "hello".to_string());
+    fn test_rendering_header() {
+        let mut game_state = game("hello", &["hello", "world", "hi"]);
+        game_state.hard_mode = true;
+        game_state.show_header = true;
+        let mut buffer = Vec::new();
+        render_game_state(&mut buffer, &game_state);
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(output.contains("5 letters, 2 valid guesses, English, hard"));
+
+        game_state.guesses.push("world".to_string());
+        let mut buffer = Vec::new();
+        render_game_state(&mut buffer, &game_state);
+        assert!(!String::from_utf8(buffer).unwrap().contains("valid guesses"));
     }
 
     #[test]
-    fn test_add_char_test_umlaut() {
-        let mut game_state =
-            super::GameState::new("hello".to_string(), vec!["hello".to_string()], false);
-        game_state.add_char('Ü');
-        assert_eq!(game_state.current_guess, "ü".to_string());
+    fn test_banned_opener_only_on_first_turn() {
+        let mut game_state = game("hello", &["hello", "crane", "world"]);
+        game_state.banned_openers = vec!["crane".to_string()];
+        assert_eq!(
+            game_state.guess("crane".to_string()),
+            Err(GameError::BannedOpener)
+        );
+        assert!(game_state.guesses.is_empty());
+        assert_eq!(game_state.guess("world".to_string()), Ok(false));
+        assert_eq!(game_state.guess("crane".to_string()), Ok(false));
+        assert_eq!(
+            GameError::BannedOpener.to_string(),
+            "This opener is banned, try another word"
+        );
     }
 
     #[test]
-    fn test_add_char_test_umlaut_length() {
-        let mut game_state =
-            super::GameState::new("hello".to_string(), vec!["hello".to_string()], false);
-        game_state.add_char('Ü');
-        game_state.add_char('Ü');
-        game_state.add_char('Ü');
-        game_state.add_char('Ü');
-        game_state.add_char('Ü');
-        assert_eq!(game_state.current_guess, "üüüüü".to_string());
+    fn test_reveal_delay_gate() {
+        assert_eq!(RevealDelay::parse("0"), Some(RevealDelay::Immediate));
+        assert_eq!(
+            RevealDelay::parse("1500"),
+            Some(RevealDelay::Pause(Duration::from_millis(1500)))
+        );
+        assert_eq!(RevealDelay::parse("key"), Some(RevealDelay::Key));
+        assert_eq!(RevealDelay::parse("soon"), None);
+        let mut game_state = game("hello", &["hello", "jolly"]);
+        game_state.reveal_delay = RevealDelay::Key;
+        // quitting shows the word right away
+        assert_eq!(game_state.reveal_gate(), RevealDelay::Immediate);
+        game_state.guesses = vec!["jolly".to_string(); 6];
+        assert_eq!(game_state.reveal_gate(), RevealDelay::Key);
+        game_state.reveal_delay = RevealDelay::parse("0").unwrap();
+        assert_eq!(game_state.reveal_gate(), RevealDelay::Immediate);
+        game_state.reveal_delay = RevealDelay::Key;
+        game_state.guesses[5] = "hello".to_string();
+        assert_eq!(game_state.reveal_gate(), RevealDelay::Immediate);
     }
 
     #[test]
-    fn test_add_char_converts_to_lowercase() {
-        let mut game_state =
-            super::GameState::new("hello".to_string(), vec!["hello".to_string()], false);
-        game_state.add_char('H');
-        assert_eq!(game_state.current_guess, "h".to_string());
-        game_state.add_char('E');
-        assert_eq!(game_state.current_guess, "he".to_string());
+    fn test_interface_falls_back_to_plain() {
+        assert_eq!(
+            choose_interface(Mode::Local, true, true).unwrap(),
+            Interface::Terminal
+        );
+        assert!(matches!(
+            choose_interface(Mode::Local, false, false),
+            Ok(Interface::Plain(_))
+        ));
+        assert!(matches!(
+            choose_interface(Mode::Local, true, false),
+            Ok(Interface::Plain(_))
+        ));
     }
 
     #[test]
-    fn test_back() {
-        let mut game_state =
-            super::GameState::new("hello".to_string(), vec!["hello".to_string()], false);
-        game_state.add_char('h');
-        assert_eq!(game_state.current_guess, "h".to_string());
-        game_state.add_char('e');
-        assert_eq!(game_state.current_guess, "he".to_string());
-        game_state.add_char('l');
-        assert_eq!(game_state.current_guess, "hel".to_string());
-        game_state.back();
-        assert_eq!(game_state.current_guess, "he".to_string());
+    fn test_non_interactive_modes_skip_the_plain_game() {
+        let mode =
+            |args: &[&str]| Mode::from_matches(&cli("test").try_get_matches_from(args).unwrap());
+        assert_eq!(mode(&["rordle"]), Mode::Local);
+        assert_eq!(mode(&["rordle", "--benchmark"]), Mode::Benchmark);
+        assert_eq!(
+            mode(&["rordle", "--watch", "--solver-log", "out.jsonl"]),
+            Mode::Watch
+        );
+        assert_eq!(
+            choose_interface(Mode::Benchmark, false, false).unwrap(),
+            Interface::Headless
+        );
+        for mode in [Mode::Watch, Mode::Connect, Mode::Duel] {
+            let error = choose_interface(mode, false, false).unwrap_err();
+            assert!(matches!(error, RustleError::Usage(_)));
+            assert!(error.to_string().contains("needs a terminal"));
+            assert_eq!(
+                choose_interface(mode, true, true).unwrap(),
+                Interface::Terminal
+            );
+        }
     }
 
     #[test]
-    fn test_cofirm_with_too_few_chars() {
-        let mut game_state =
-            super::GameState::new("hello".to_string(), vec!["hello".to_string()], false);
-        game_state.add_char('h');
-        game_state.add_char('e');
+    fn test_plain_game() {
+        let mut game_state = game("hello", &["hello", "jolly", "world"]);
+        let mut output = Vec::new();
+        plain_game(
+            &mut game_state,
+            "jolly\nxyz\nHello\nworld\n".as_bytes(),
+            &mut output,
+        );
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "Guess the 5 letter word, one guess per line\n\
+             jolly .YGG.\n\
+             xyz: Word is not the correct length\n\
+             hello GGGGG\n\
+             You won in 2 guesses!\n"
+        );
+    }
+
+    #[test]
+    fn test_auto_hint_only_on_last_try() {
+        let mut game_state = game("hello", &["hello", "jolly", "world"]);
+        game_state.auto_hint = true;
+        for tries in (2..=6).rev() {
+            assert_eq!(game_state.remaining_tries(), tries);
+            assert!(!game_state.auto_hint());
+            game_state.guesses.push("jolly".to_string());
+        }
+        assert_eq!(game_state.remaining_tries(), 1);
+        assert!(game_state.auto_hint());
+        // jolly found the l's, so the first letter is revealed
+        assert_eq!(game_state.hints, vec![0]);
+        assert_eq!(
+            game_state.notice.as_deref(),
+            Some("Last try! Letter 1 is H")
+        );
+        assert!(!game_state.auto_hint());
+
+        let mut game_state = game_state.clone();
+        game_state.retry_same_word();
+        game_state.auto_hint = false;
+        game_state.guesses = vec!["jolly".to_string(); 5];
+        assert!(!game_state.auto_hint());
+    }
+
+    #[test]
+    fn test_undo_last_guess() {
+        let mut game_state = game("hello", &["hello", "jolly", "world"]);
+        game_state.guesses = vec!["jolly".to_string(), "hello".to_string()];
+        assert!(!game_state.undo_last_guess());
+        game_state.max_mulligans = 1;
+        assert!(game_state.won() && game_state.is_over());
+        assert!(game_state.undo_last_guess());
+        assert_eq!(game_state.guesses, vec!["jolly"]);
+        assert!(!game_state.won());
+        assert!(!game_state.is_over());
+        // only one mulligan per game
+        assert!(!game_state.undo_last_guess());
+        assert_eq!(game_state.guesses.len(), 1);
+
+        game_state.retry_same_word();
+        game_state.guesses = vec!["world".to_string(); 6];
+        assert!(game_state.is_over());
+        assert!(game_state.undo_last_guess());
+        assert!(!game_state.is_over());
+        assert_eq!(game_state.guesses.len(), 5);
+    }
+
+    #[test]
+    fn test_answer_only_forbidden_as_opener() {
+        let mut game_state = game("hello", &["hello", "world"]);
+        game_state.forbid_answer_opener = true;
+        assert_eq!(
+            game_state.guess("hello".to_string()),
+            Err(GameError::AnswerAsOpener)
+        );
+        assert_eq!(game_state.guess("world".to_string()), Ok(false));
+        assert_eq!(game_state.guess("hello".to_string()), Ok(true));
+
+        let mut game_state = game("hello", &["hello", "world"]);
+        assert_eq!(game_state.guess("hello".to_string()), Ok(true));
+    }
+
+    #[test]
+    fn test_unique_letters_rule() {
+        let mut game_state = game("world", &["crane", "allee", "world"]);
+        game_state.unique_letters = true;
+        assert_eq!(
+            game_state.guess("allee".to_string()),
+            Err(GameError::RepeatedLetters)
+        );
+        assert_eq!(game_state.guess("crane".to_string()), Ok(false));
+        assert_eq!(
+            GameError::RepeatedLetters.to_string(),
+            "Word must not repeat a letter"
+        );
+    }
+
+    #[test]
+    fn test_anagram_rule() {
+        let mut game_state = game("slate", &["stale", "least", "slate", "crane"]);
+        game_state.anagram = true;
+        assert_eq!(game_state.guess("stale".to_string()), Ok(false));
+        assert_eq!(
+            game_state.guess("crane".to_string()),
+            Err(GameError::NotAnagram)
+        );
+        assert_eq!(
+            game_state.guess("stale".to_string()),
+            Err(GameError::NotAnagram)
+        );
+        assert_eq!(game_state.guess("least".to_string()), Ok(false));
+        game_state.forfeit_turn(Instant::now());
+        assert_eq!(game_state.guess("slate".to_string()), Ok(true));
+        assert_eq!(
+            GameError::NotAnagram.to_string(),
+            "Word must rearrange the letters of the last guess"
+        );
+    }
+
+    #[test]
+    fn test_only_the_first_game_plays_the_challenge() {
+        let code = challenge::encode(&ChallengeSpec {
+            puzzle: Puzzle::Answer(42),
+            length: 5,
+            guesses: None,
+        });
+        let matches = cli("test")
+            .try_get_matches_from(["rordle", "--challenge", &code])
+            .unwrap();
+        let first = new_game(&matches, &Config::default(), &[], true).unwrap();
+        assert!(first.challenge.is_some());
+        assert_eq!(first.word, words::PICKED_WORDS.lines().nth(42).unwrap());
+        let next = new_game(&matches, &Config::default(), &[], false).unwrap();
+        assert!(next.challenge.is_none());
+
+        let matches = cli("test")
+            .try_get_matches_from(["rordle", "--seed", "7"])
+            .unwrap();
+        let first = new_game(&matches, &Config::default(), &[], true).unwrap();
+        assert_eq!(first.seed, Some(7));
+        let next = new_game(&matches, &Config::default(), &[], false).unwrap();
+        assert!(next.seed.is_some());
+        assert_ne!(next.seed, Some(7));
+    }
+
+    #[test]
+    fn test_challenge_starts_expected_puzzle() {
+        let code = challenge::encode(&ChallengeSpec {
+            puzzle: Puzzle::Answer(42),
+            length: 5,
+            guesses: Some(4),
+        });
+        let spec = challenge::decode_challenge(&code).unwrap();
+        let today = Date::parse("2022-02-01").unwrap();
+        let mut options = GameOptions::default();
+        assert_eq!(apply_challenge(&spec, &mut options, today), Ok(None));
+        let game_state = init_game(&options, &mut rand::thread_rng()).unwrap();
+        assert_eq!(
+            game_state.word,
+            words::PICKED_WORDS.lines().nth(42).unwrap()
+        );
+        assert_eq!(check_challenge(&spec, &game_state), Ok(()));
+
+        let daily = ChallengeSpec {
+            puzzle: Puzzle::Daily(196),
+            length: 5,
+            guesses: None,
+        };
+        let mut options = GameOptions::default();
+        let game = apply_challenge(&daily, &mut options, today)
+            .unwrap()
+            .unwrap();
+        assert_eq!(game.date, Date::parse("2022-01-01").unwrap());
+        assert_eq!(options.answer_index, Some(196));
+        let future = ChallengeSpec {
+            puzzle: Puzzle::Daily(1000),
+            ..daily
+        };
+        assert!(apply_challenge(&future, &mut options, today).is_err());
+
+        let too_far = ChallengeSpec {
+            puzzle: Puzzle::Answer(100_000),
+            ..spec
+        };
+        assert!(check_challenge(&too_far, &game_state).is_err());
+    }
+
+    #[test]
+    fn test_adjust_tries_is_clamped() {
+        assert_eq!(adjust_tries(6, true), 7);
+        assert_eq!(adjust_tries(6, false), 5);
+        assert_eq!(adjust_tries(MAX_TRIES, true), MAX_TRIES);
+        assert_eq!(adjust_tries(MIN_TRIES, false), MIN_TRIES);
+        // values set elsewhere are pulled back into the range
+        assert_eq!(adjust_tries(0, true), MIN_TRIES);
+        assert_eq!(adjust_tries(u16::MAX, true), MAX_TRIES);
+    }
+
+    #[test]
+    fn test_wildcard_pattern_is_not_a_guess() {
+        let mut game_state = game("crate", &["crane", "crate", "chase", "grace"]);
+        for c in "c.a.e".chars() {
+            game_state.add_char(c);
+        }
+        assert_eq!(game_state.wildcard_count(), None);
+        game_state.assist = true;
         game_state.confirm();
-        assert_eq!(game_state.last_error.unwrap(), GameError::WrongLength);
-        assert_eq!(game_state.current_guess.len(), 0);
-        assert_eq!(game_state.guesses.len(), 0);
+        assert!(game_state.guesses.is_empty());
+        assert_eq!(game_state.current_guess, "c.a.e");
+        assert_eq!(
+            game_state.notice.as_deref(),
+            Some("3 possible answers match c.a.e")
+        );
+        game_state.guesses.push("grace".to_string());
+        assert_eq!(
+            game_state.known_greens(),
+            vec![None, Some('r'), Some('a'), None, Some('e')]
+        );
+        assert_eq!(game_state.wildcard_count(), Some(2));
     }
 
     #[test]
-    fn test_cofirm_with_invalid_word() {
-        let mut game_state =
-            super::GameState::new("hello".to_string(), vec!["hello".to_string()], false);
-        game_state.add_char('j');
-        game_state.add_char('e');
-        game_state.add_char('l');
-        game_state.add_char('l');
-        game_state.add_char('o');
+    fn test_typo_suggests_nearest_word() {
+        let mut game_state = game("hello", &["hello", "jolly", "world", "belly"]);
+        assert_eq!(game_state.nearest_valid("jplly"), Some("jolly".to_string()));
+        assert_eq!(game_state.nearest_valid("crane"), None);
+
+        game_state.current_guess = "wprld".to_string();
         game_state.confirm();
-        assert_eq!(game_state.last_error.unwrap(), GameError::InvalidWord);
-        assert_eq!(game_state.current_guess.len(), 0);
-        assert_eq!(game_state.guesses.len(), 0);
+        assert_eq!(
+            game_state.error_message().as_deref(),
+            Some("Word is not valid")
+        );
+        game_state.suggest_words = true;
+        game_state.current_guess = "wprld".to_string();
+        game_state.confirm();
+        assert_eq!(
+            game_state.error_message().as_deref(),
+            Some("Word is not valid, did you mean \"world\"?")
+        );
+        game_state.add_char('w');
+        assert_eq!(game_state.error_message(), None);
     }
 
     #[test]
-    fn test_cofirm() {
-        let mut game_state =
-            super::GameState::new("hello".to_string(), vec!["hello".to_string()], false);
+    fn test_enter_on_partial_row() {
+        let mut game_state = game("hello", &["hello"]);
         game_state.add_char('h');
-        // produce error
         game_state.confirm();
-        game_state.add_char('h');
+        assert_eq!(game_state.last_error, Some(GameError::WrongLength));
+
+        game_state.reset_error();
+        game_state.ignore_partial_enter = true;
         game_state.add_char('e');
-        game_state.add_char('l');
-        game_state.add_char('l');
-        game_state.add_char('o');
         game_state.confirm();
         assert_eq!(game_state.last_error, None);
-        assert_eq!(game_state.current_guess.len(), 0);
-        assert_eq!(game_state.guesses.len(), 1);
+        assert_eq!(game_state.current_guess, "e");
+        assert_eq!(game_state.invalid_attempts, 1);
     }
 
     #[test]
-    fn test_accepts_any_word() {
-        let mut game_state = super::GameState::new(
-            "hello".to_string(),
-            vec!["hello".to_string(), "jolly".to_string()],
-            true,
+    fn test_keep_invalid_input() {
+        let mut game_state = game("hello", &["hello", "jolly"]);
+        game_state.keep_invalid_input = true;
+        for c in "jollx".chars() {
+            game_state.add_char(c);
+        }
+        game_state.confirm();
+        assert_eq!(game_state.last_error, Some(GameError::InvalidWord));
+        assert_eq!(game_state.current_guess, "jollx");
+        assert!(game_state.guesses.is_empty());
+
+        game_state.back();
+        game_state.add_char('y');
+        game_state.confirm();
+        assert_eq!(game_state.last_error, None);
+        assert_eq!(game_state.guesses, vec!["jolly"]);
+        assert_eq!(game_state.current_guess, "");
+
+        // a row of the wrong length is still cleared
+        game_state.add_char('h');
+        game_state.confirm();
+        assert_eq!(game_state.last_error, Some(GameError::WrongLength));
+        assert_eq!(game_state.current_guess, "");
+    }
+
+    #[test]
+    fn test_first_hits() {
+        let mut game_state = game("hello", &["hello", "jolly", "cello", "world"]);
+        assert_eq!(game_state.first_hits(), vec![None; 5]);
+        game_state.guesses = vec![
+            "world".to_string(),
+            "jolly".to_string(),
+            "cello".to_string(),
+        ];
+        assert_eq!(
+            game_state.first_hits(),
+            vec![None, Some(3), Some(2), Some(1), Some(3)]
         );
-        let result = game_state.guess("milli".to_string()).unwrap();
-        assert_eq!(result, false);
+        let mut buffer = Vec::new();
+        render_first_hits(&mut buffer, &game_state);
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(output.contains(&format!("{} - ", game_state.theme.miss)));
+        assert!(output.contains(&format!("{} 3 ", game_state.theme.hit)));
+    }
+
+    #[test]
+    fn test_init_game_with_answer_index() {
+        let first = init_game(
+            &GameOptions {
+                answer_index: Some(200),
+                ..Default::default()
+            },
+            &mut rand::thread_rng(),
+        )
+        .unwrap();
+        let second = init_game(
+            &GameOptions {
+                answer_index: Some(200),
+                ..Default::default()
+            },
+            &mut rand::thread_rng(),
+        )
+        .unwrap();
+        assert_eq!(first.word, second.word);
+        assert_eq!(first.word, first.answers[200]);
+    }
+
+    #[test]
+    fn test_init_game_with_stubbed_rng() {
+        let mut rng = rand::rngs::mock::StepRng::new(7, 0);
+        let game_state = init_game(&GameOptions::default(), &mut rng).unwrap();
+        assert_eq!(game_state.seed, Some(7));
+        let index = answer_for_seed(&game_state.answers, &HashMap::new(), 7);
+        assert_eq!(game_state.word, game_state.answers[index]);
+    }
+
+    #[test]
+    fn test_game_id_round_trips() {
+        let game_state = init_game(&GameOptions::default(), &mut rand::thread_rng()).unwrap();
+        let id = game_state.game_id().unwrap();
+        let seed = id.strip_prefix("seed ").unwrap().parse::<u64>().unwrap();
+        let replay = init_game(
+            &GameOptions {
+                seed: Some(seed),
+                ..Default::default()
+            },
+            &mut rand::thread_rng(),
+        )
+        .unwrap();
+        assert_eq!(replay.word, game_state.word);
+        assert_eq!(replay.game_id(), Some(id));
+
+        // daily puzzles are identified by their number, which is also in the challenge code
+        let mut daily = replay;
+        daily.daily = DailyGame::for_date(Date::today(), Date::today()).ok();
+        let index = daily.daily.unwrap().index;
+        assert_eq!(daily.game_id(), Some(format!("daily #{}", index)));
+        daily.word = daily.answers[index % daily.answers.len()].clone();
+        let spec =
+            challenge::decode_challenge(&challenge::encode_challenge(&daily).unwrap()).unwrap();
+        assert_eq!(spec.puzzle, Puzzle::Daily(index));
+
+        let mut fixed = daily;
+        fixed.daily = None;
+        fixed.set_word("hello").unwrap();
+        assert_eq!(fixed.game_id(), None);
+    }
+
+    #[test]
+    fn test_min_frequency_excludes_rare_guesses() {
+        let path = std::env::temp_dir().join(format!("rustle-min-freq-{}.txt", std::process::id()));
+        std::fs::write(
+            &path,
+            "hello 50
+world 20
+xysti 0.01
+fuzzy
+",
+        )
+        .unwrap();
+        let mut game_state = init_game(
+            &GameOptions {
+                word_files: &[path.to_str().unwrap().to_string()],
+                answer_index: Some(0),
+                min_frequency: Some(1.0),
+                ..Default::default()
+            },
+            &mut rand::thread_rng(),
+        )
+        .unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(game_state.word, "hello");
+        assert_eq!(game_state.answers.len(), 4);
+        game_state.current_guess = "xysti".to_string();
+        game_state.confirm();
+        assert_eq!(game_state.last_error, Some(GameError::InvalidWord));
+        game_state.current_guess = "world".to_string();
+        game_state.confirm();
+        assert_eq!(game_state.last_error, None);
+        // words without a frequency are kept
+        game_state.current_guess = "fuzzy".to_string();
+        game_state.confirm();
+        assert_eq!(game_state.last_error, None);
+    }
+
+    #[test]
+    fn test_pick_answer_weighted_by_frequency() {
+        let answers = vec!["common".to_string(), "rare".to_string()];
+        let frequencies: HashMap<String, f64> =
+            [("common".to_string(), 9.0), ("rare".to_string(), 1.0)].into();
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut counts = [0; 2];
+        for _ in 0..1000 {
+            counts[pick_answer(&answers, &frequencies, &[], &mut rng).0] += 1;
+        }
+        assert!(counts[0] > counts[1] * 3, "{:?}", counts);
+    }
+
+    #[test]
+    fn test_pick_answer_avoids_recent_answers() {
+        let answers: Vec<String> = ["hello", "world", "jolly"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let mut rng = StdRng::seed_from_u64(42);
+        let recent = vec!["hello".to_string(), "jolly".to_string()];
+        for _ in 0..100 {
+            assert_eq!(
+                pick_answer(&answers, &HashMap::new(), &recent, &mut rng).0,
+                1
+            );
+        }
+        let frequencies: HashMap<String, f64> = [("hello".to_string(), 100.0)].into();
+        assert_eq!(pick_answer(&answers, &frequencies, &recent, &mut rng).0, 1);
+        // every answer was used recently, so repeats are allowed again
+        let recent = answers.clone();
+        let mut counts = [0; 3];
+        for _ in 0..300 {
+            counts[pick_answer(&answers, &HashMap::new(), &recent, &mut rng).0] += 1;
+        }
+        assert!(counts.iter().all(|count| *count > 0), "{:?}", counts);
+    }
+
+    #[test]
+    fn test_difficulty_label() {
+        assert_eq!(difficulty_label(250.0), "common");
+        assert_eq!(difficulty_label(10.0), "common");
+        assert_eq!(difficulty_label(9.9), "uncommon");
+        assert_eq!(difficulty_label(1.0), "uncommon");
+        assert_eq!(difficulty_label(0.5), "rare");
+        assert_eq!(difficulty_label(0.0), "rare");
+    }
+
+    #[test]
+    fn test_pick_answer_uniform_without_frequencies() {
+        let answers = vec!["hello".to_string(), "world".to_string()];
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut counts = [0; 2];
+        for _ in 0..1000 {
+            counts[pick_answer(&answers, &HashMap::new(), &[], &mut rng).0] += 1;
+        }
+        assert!(counts[0] > 400 && counts[1] > 400, "{:?}", counts);
+    }
+
+    #[test]
+    fn test_archive_games_do_not_count_toward_streak() {
+        let today = Date::parse("2022-02-01").unwrap();
+        let archive = Date::parse("2022-01-10").unwrap();
+        let daily = DailyGame::for_date(archive, today).unwrap();
+        let mut game_state = init_game(
+            &GameOptions {
+                answer_index: Some(daily.index),
+                ..Default::default()
+            },
+            &mut rand::thread_rng(),
+        )
+        .unwrap();
+        game_state.daily = Some(daily);
+        assert!(!game_state.counts_toward_streak());
+        game_state.daily = Some(DailyGame::for_date(today, today).unwrap());
+        assert!(game_state.counts_toward_streak());
+    }
+
+    #[test]
+    fn test_reload_words_swaps_valid_words() {
+        let path = std::env::temp_dir().join(format!("rustle-reload-{}.txt", std::process::id()));
+        std::fs::write(&path, "hello\nworld\n").unwrap();
+        let mut game_state = init_game(
+            &GameOptions {
+                word_files: &[path.to_str().unwrap().to_string()],
+                answer_index: Some(0),
+                ..Default::default()
+            },
+            &mut rand::thread_rng(),
+        )
+        .unwrap();
+        assert_eq!(game_state.word, "hello");
+        game_state.keyboard = Some(Keyboard::for_words(&game_state.valid_words));
+        std::fs::write(&path, "jolly 5\nfüzzy\n").unwrap();
+        assert_eq!(game_state.reload_words().unwrap(), 3);
+        assert_eq!(game_state.valid_words, vec!["jolly", "füzzy", "hello"]);
+        assert!(game_state
+            .keyboard
+            .as_ref()
+            .unwrap()
+            .letters()
+            .contains(&'ü'));
+        assert_eq!(game_state.word, "hello");
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(game_state.reload_words().is_err());
+        assert_eq!(game_state.valid_words.len(), 3);
+        game_state.current_guess = "world".to_string();
+        game_state.confirm();
+        assert_eq!(game_state.last_error, Some(GameError::InvalidWord));
+        game_state.current_guess = "jolly".to_string();
+        game_state.confirm();
+        assert_eq!(game_state.guesses, vec!["jolly"]);
+
+        let mut embedded = init_game(&GameOptions::default(), &mut rand::thread_rng()).unwrap();
+        assert!(embedded.reload_words().is_err());
+    }
+
+    #[test]
+    fn test_reload_words_keeps_alphabet_and_favorites() {
+        let path =
+            std::env::temp_dir().join(format!("rustle-reload-filters-{}.txt", std::process::id()));
+        std::fs::write(&path, "hello\nholle\n").unwrap();
+        let favorites = vec!["lolhe".to_string()];
+        let mut game_state = init_game(
+            &GameOptions {
+                word_files: &[path.to_str().unwrap().to_string()],
+                alphabet: Some("helo"),
+                favorites: &favorites,
+                answer_index: Some(0),
+                ..Default::default()
+            },
+            &mut rand::thread_rng(),
+        )
+        .unwrap();
+        std::fs::write(&path, "holle\njolly\nhelle\n").unwrap();
+        game_state.reload_words().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(game_state.word, "hello");
+        // "jolly" uses letters outside the alphabet, the favorite stays valid
+        assert_eq!(
+            game_state.valid_words,
+            vec!["holle", "helle", "lolhe", "hello"]
+        );
+    }
+
+    #[test]
+    fn test_daily_season_requires_a_daily_puzzle() {
+        assert!(cli("test")
+            .try_get_matches_from(["rordle", "--daily-season", "2"])
+            .is_err());
+        assert!(cli("test")
+            .try_get_matches_from(["rordle", "--daily", "--daily-season", "2"])
+            .is_ok());
+        assert!(cli("test")
+            .try_get_matches_from([
+                "rordle",
+                "--daily-date",
+                "2022-03-01",
+                "--daily-season",
+                "2"
+            ])
+            .is_ok());
+
+        let options = GameOptions {
+            answer_index: Some(3),
+            daily_season: Some(2),
+            ..Default::default()
+        };
+        let mut game_state = init_game(&options, &mut rand::thread_rng()).unwrap();
+        game_state.daily = Some(DailyGame::for_date(daily_date(3), Date::today()).unwrap());
+        assert_eq!(challenge::encode_challenge(&game_state), None);
+    }
+
+    #[test]
+    fn test_missing_word_list_is_a_word_list_error() {
+        let missing =
+            std::env::temp_dir().join(format!("rustle-missing-words-{}.txt", std::process::id()));
+        let missing = missing.to_str().unwrap();
+        let matches = cli("test")
+            .try_get_matches_from(["rordle", "--word-file", missing])
+            .unwrap();
+        let error = new_game(&matches, &Config::default(), &[], true)
+            .err()
+            .unwrap();
+        assert!(matches!(
+            error,
+            RustleError::WordList {
+                list: "word list",
+                ..
+            }
+        ));
+        assert_eq!(error.exit_code(), 1);
+
+        let matches = cli("test")
+            .try_get_matches_from(["rordle", "--favorites", missing])
+            .unwrap();
+        let error = new_game(&matches, &Config::default(), &[], true)
+            .err()
+            .unwrap();
+        assert!(matches!(
+            error,
+            RustleError::WordList {
+                list: "favorite words",
+                ..
+            }
+        ));
+
+        let matches = cli("test")
+            .try_get_matches_from(["rordle", "--seed", "abc"])
+            .unwrap();
+        let error = new_game(&matches, &Config::default(), &[], true)
+            .err()
+            .unwrap();
+        assert!(matches!(error, RustleError::Usage(_)));
+        assert_eq!(error.exit_code(), 2);
     }
 
     #[test]
@@ -547,13 +5292,13 @@ mod tests {
         let mut game_state =
             super::GameState::new("hello".to_string(), vec!["hello".to_string()], false);
         game_state.add_char('Ü');
-        render_game_state(&game_state);
+        render_game_state(&mut Vec::new(), &game_state);
     }
     #[test]
     fn test_rendering_with_one_input() {
         let mut game_state =
             super::GameState::new("hello".to_string(), vec!["hello".to_string()], false);
         game_state.add_char('w');
-        render_game_state(&game_state);
+        render_game_state(&mut Vec::new(), &game_state);
     }
 }
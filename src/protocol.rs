@@ -0,0 +1,257 @@
+//! Line based text protocol used by the multiplayer race.
+//!
+//! Every message is a single line: a command followed by space separated arguments.
+//!
+//! Client to server:
+//! - `HELLO <name>` join the race, must be sent first
+//! - `GUESS <word>` submit a guess
+//!
+//! Server to client:
+//! - `WELCOME <length> <max_tries>` reply to `HELLO`
+//! - `RESULT <word> <pattern>` feedback for a guess, the pattern uses
+//!   `G` for a hit, `Y` for a contained letter and `.` for a miss
+//! - `ERROR <message>` the last message was rejected
+//! - `PROGRESS <name> <guesses>` broadcast after every valid guess of a player
+//! - `SOLVED <name>` broadcast when the first player solved the word
+//! - `OVER <word>` the player has used all tries, reveals the word
+
+use crate::HitInfo;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientMessage {
+    Hello(String),
+    Guess(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerMessage {
+    Welcome(usize, u16),
+    Result(String, Vec<HitInfo>),
+    Error(String),
+    Progress(String, usize),
+    Solved(String),
+    Over(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProtocolError {
+    UnknownCommand(String),
+    MissingArgument,
+    InvalidArgument(String),
+}
+
+impl std::fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ProtocolError::UnknownCommand(cmd) => write!(f, "Unknown command: {}", cmd),
+            ProtocolError::MissingArgument => write!(f, "Missing argument"),
+            ProtocolError::InvalidArgument(arg) => write!(f, "Invalid argument: {}", arg),
+        }
+    }
+}
+
+pub fn encode_hits(hits: &[HitInfo]) -> String {
+    hits.iter()
+        .map(|hit| match hit {
+            HitInfo::Hit => 'G',
+            HitInfo::Contains => 'Y',
+            _ => '.',
+        })
+        .collect()
+}
+
+pub fn decode_hits(pattern: &str) -> Result<Vec<HitInfo>, ProtocolError> {
+    pattern
+        .chars()
+        .map(|c| match c {
+            'G' => Ok(HitInfo::Hit),
+            'Y' => Ok(HitInfo::Contains),
+            '.' => Ok(HitInfo::Miss),
+            _ => Err(ProtocolError::InvalidArgument(pattern.to_string())),
+        })
+        .collect()
+}
+
+/// Splits a line into its command and the remaining arguments.
+fn split_command(line: &str) -> (&str, Vec<&str>) {
+    let mut parts = line.split_whitespace();
+    let command = parts.next().unwrap_or("");
+    (command, parts.collect())
+}
+
+fn argument<'a>(args: &[&'a str], index: usize) -> Result<&'a str, ProtocolError> {
+    args.get(index)
+        .copied()
+        .ok_or(ProtocolError::MissingArgument)
+}
+
+fn number<T: std::str::FromStr>(arg: &str) -> Result<T, ProtocolError> {
+    arg.parse()
+        .map_err(|_| ProtocolError::InvalidArgument(arg.to_string()))
+}
+
+impl ClientMessage {
+    pub fn parse(line: &str) -> Result<ClientMessage, ProtocolError> {
+        let (command, args) = split_command(line);
+        match command {
+            "HELLO" => Ok(ClientMessage::Hello(argument(&args, 0)?.to_string())),
+            "GUESS" => Ok(ClientMessage::Guess(argument(&args, 0)?.to_lowercase())),
+            _ => Err(ProtocolError::UnknownCommand(command.to_string())),
+        }
+    }
+}
+
+impl std::fmt::Display for ClientMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ClientMessage::Hello(name) => write!(f, "HELLO {}", name),
+            ClientMessage::Guess(word) => write!(f, "GUESS {}", word),
+        }
+    }
+}
+
+impl ServerMessage {
+    pub fn parse(line: &str) -> Result<ServerMessage, ProtocolError> {
+        let (command, args) = split_command(line);
+        match command {
+            "WELCOME" => Ok(ServerMessage::Welcome(
+                number(argument(&args, 0)?)?,
+                number(argument(&args, 1)?)?,
+            )),
+            "RESULT" => Ok(ServerMessage::Result(
+                argument(&args, 0)?.to_string(),
+                decode_hits(argument(&args, 1)?)?,
+            )),
+            "ERROR" => Ok(ServerMessage::Error(args.join(" "))),
+            "PROGRESS" => Ok(ServerMessage::Progress(
+                argument(&args, 0)?.to_string(),
+                number(argument(&args, 1)?)?,
+            )),
+            "SOLVED" => Ok(ServerMessage::Solved(argument(&args, 0)?.to_string())),
+            "OVER" => Ok(ServerMessage::Over(argument(&args, 0)?.to_string())),
+            _ => Err(ProtocolError::UnknownCommand(command.to_string())),
+        }
+    }
+}
+
+impl std::fmt::Display for ServerMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ServerMessage::Welcome(length, max_tries) => {
+                write!(f, "WELCOME {} {}", length, max_tries)
+            }
+            ServerMessage::Result(word, hits) => write!(f, "RESULT {} {}", word, encode_hits(hits)),
+            ServerMessage::Error(message) => write!(f, "ERROR {}", message),
+            ServerMessage::Progress(name, guesses) => write!(f, "PROGRESS {} {}", name, guesses),
+            ServerMessage::Solved(name) => write!(f, "SOLVED {}", name),
+            ServerMessage::Over(word) => write!(f, "OVER {}", word),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_client_messages() {
+        assert_eq!(
+            ClientMessage::parse("HELLO niko"),
+            Ok(ClientMessage::Hello("niko".to_string()))
+        );
+        assert_eq!(
+            ClientMessage::parse("GUESS Hello\r"),
+            Ok(ClientMessage::Guess("hello".to_string()))
+        );
+        assert_eq!(
+            ClientMessage::parse("GUESS"),
+            Err(ProtocolError::MissingArgument)
+        );
+        assert_eq!(
+            ClientMessage::parse("JUMP high"),
+            Err(ProtocolError::UnknownCommand("JUMP".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_client_message_round_trip() {
+        let messages = vec![
+            ClientMessage::Hello("niko".to_string()),
+            ClientMessage::Guess("hello".to_string()),
+        ];
+        for message in messages {
+            let line = message.to_string();
+            assert_eq!(ClientMessage::parse(&line), Ok(message));
+        }
+    }
+
+    #[test]
+    fn test_serialize_server_messages() {
+        let hits = vec![
+            HitInfo::Miss,
+            HitInfo::Contains,
+            HitInfo::Hit,
+            HitInfo::Hit,
+            HitInfo::Miss,
+        ];
+        assert_eq!(ServerMessage::Welcome(5, 6).to_string(), "WELCOME 5 6");
+        assert_eq!(
+            ServerMessage::Result("jolly".to_string(), hits).to_string(),
+            "RESULT jolly .YGG."
+        );
+        assert_eq!(
+            ServerMessage::Error("Word is not valid".to_string()).to_string(),
+            "ERROR Word is not valid"
+        );
+        assert_eq!(
+            ServerMessage::Solved("niko".to_string()).to_string(),
+            "SOLVED niko"
+        );
+        assert_eq!(
+            ServerMessage::Over("hello".to_string()).to_string(),
+            "OVER hello"
+        );
+    }
+
+    #[test]
+    fn test_server_message_round_trip() {
+        let messages = vec![
+            ServerMessage::Welcome(5, 6),
+            ServerMessage::Result(
+                "hello".to_string(),
+                vec![HitInfo::Hit, HitInfo::Contains, HitInfo::Miss],
+            ),
+            ServerMessage::Error("Word is not valid".to_string()),
+            ServerMessage::Progress("niko".to_string(), 3),
+            ServerMessage::Solved("niko".to_string()),
+            ServerMessage::Over("hello".to_string()),
+        ];
+        for message in messages {
+            let line = message.to_string();
+            assert_eq!(ServerMessage::parse(&line), Ok(message));
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid_server_messages() {
+        assert_eq!(
+            ServerMessage::parse("WELCOME five 6"),
+            Err(ProtocolError::InvalidArgument("five".to_string()))
+        );
+        assert_eq!(
+            ServerMessage::parse("RESULT hello GGXGG"),
+            Err(ProtocolError::InvalidArgument("GGXGG".to_string()))
+        );
+        assert_eq!(
+            ServerMessage::parse("PROGRESS niko"),
+            Err(ProtocolError::MissingArgument)
+        );
+    }
+
+    #[test]
+    fn test_encode_hits() {
+        let hits = vec![HitInfo::Hit, HitInfo::Contains, HitInfo::Miss];
+        assert_eq!(encode_hits(&hits), "GY.");
+        assert_eq!(decode_hits("GY.").unwrap(), hits);
+    }
+}
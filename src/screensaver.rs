@@ -0,0 +1,100 @@
+//! Drifting tiles shown while the prompt after a game sits idle.
+
+use crate::theme::Theme;
+use crate::{timer, HitInfo};
+use std::fs::File;
+use std::io::Write;
+use std::time::Duration;
+
+/// Tiles on the screen at the same time.
+pub const TILES: usize = 12;
+/// Time between frames, slow enough to keep the CPU idle.
+const FRAME: Duration = Duration::from_millis(200);
+
+/// A tile of a frame, `x` and `y` are terminal coordinates starting at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tile {
+    pub x: u16,
+    pub y: u16,
+    pub hit: HitInfo,
+}
+
+/// Tiles of frame number `tick` on a `width` x `height` screen. Every tile
+/// drifts diagonally on its own course and wraps around at the edges.
+pub fn frame(width: u16, height: u16, tick: u64) -> Vec<Tile> {
+    // tiles are two columns wide
+    let columns = (width as u64 / 2).max(1);
+    let rows = (height as u64).max(1);
+    (0..TILES as u64)
+        .map(|i| {
+            let column = (i * 7919 + tick * (1 + i % 2)) % columns;
+            // rows change slower than columns so the tiles drift rather than jump
+            let row = (i * 104729 + tick / (2 + i % 3)) % rows;
+            Tile {
+                x: (column * 2 + 1) as u16,
+                y: (row + 1) as u16,
+                hit: match i % 3 {
+                    0 => HitInfo::Hit,
+                    1 => HitInfo::Contains,
+                    _ => HitInfo::Miss,
+                },
+            }
+        })
+        .collect()
+}
+
+/// Animates the tiles until there is input on `tty`, the input is left unread.
+pub fn run<W: Write>(stdout: &mut W, tty: &File, theme: &Theme) {
+    let mut tick = 0;
+    loop {
+        let (width, height) = termion::terminal_size()
+            .ok()
+            .filter(|(width, height)| *width > 0 && *height > 0)
+            .unwrap_or((80, 24));
+        let mut buffer = Vec::new();
+        write!(buffer, "{}{}", termion::clear::All, termion::cursor::Hide).unwrap();
+        for tile in frame(width, height, tick) {
+            write!(
+                buffer,
+                "{}{}  {}",
+                termion::cursor::Goto(tile.x, tile.y),
+                theme.style(&tile.hit),
+                termion::style::Reset
+            )
+            .unwrap();
+        }
+        stdout.write_all(&buffer).unwrap();
+        stdout.flush().unwrap();
+        if timer::wait_for_input(tty, FRAME) {
+            break;
+        }
+        tick += 1;
+    }
+    write!(
+        stdout,
+        "{}{}{}",
+        termion::clear::All,
+        termion::cursor::Goto(1, 1),
+        termion::cursor::Show
+    )
+    .unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_has_all_tiles_on_screen() {
+        for tick in [0, 1, 57, 1000] {
+            let tiles = frame(80, 24, tick);
+            assert_eq!(tiles.len(), TILES);
+            assert!(tiles
+                .iter()
+                .all(|tile| (1..80).contains(&tile.x) && (1..=24).contains(&tile.y)));
+        }
+        assert_ne!(frame(80, 24, 0), frame(80, 24, 1));
+        // a tiny terminal still gets every tile
+        assert_eq!(frame(1, 1, 3).len(), TILES);
+    }
+}
@@ -0,0 +1,121 @@
+use crate::protocol::{ClientMessage, ServerMessage};
+use crate::GameState;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Shared state of all players racing for the same word.
+#[derive(Default)]
+struct Race {
+    players: Vec<TcpStream>,
+    solved_by: Option<String>,
+}
+
+impl Race {
+    /// Sends a message to all connected players, dropping disconnected ones.
+    fn broadcast(&mut self, message: &ServerMessage) {
+        self.players
+            .retain_mut(|stream| writeln!(stream, "{}", message).is_ok());
+    }
+}
+
+/// Hosts a race where every connection plays a copy of `template`.
+pub fn serve(port: u16, template: GameState) -> io::Result<()> {
+    let listener = TcpListener::bind(("0.0.0.0", port))?;
+    println!("Serving on port {}", port);
+    let race = Arc::new(Mutex::new(Race::default()));
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(error) => {
+                eprintln!("Connection failed: {}", error);
+                continue;
+            }
+        };
+        let game_state = template.clone();
+        let race = Arc::clone(&race);
+        thread::spawn(move || {
+            if let Err(error) = handle_client(stream, game_state, race) {
+                eprintln!("Player disconnected: {}", error);
+            }
+        });
+    }
+    Ok(())
+}
+
+fn send(stream: &mut TcpStream, message: ServerMessage) -> io::Result<()> {
+    writeln!(stream, "{}", message)
+}
+
+fn handle_client(
+    stream: TcpStream,
+    mut game_state: GameState,
+    race: Arc<Mutex<Race>>,
+) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    let mut name = None;
+    for line in BufReader::new(stream).lines() {
+        let message = match ClientMessage::parse(&line?) {
+            Ok(message) => message,
+            Err(error) => {
+                send(&mut writer, ServerMessage::Error(error.to_string()))?;
+                continue;
+            }
+        };
+        match (message, &name) {
+            (ClientMessage::Hello(player), None) => {
+                race.lock().unwrap().players.push(writer.try_clone()?);
+                send(
+                    &mut writer,
+                    ServerMessage::Welcome(game_state.word.chars().count(), game_state.max_tries),
+                )?;
+                name = Some(player);
+            }
+            (ClientMessage::Hello(_), Some(_)) => {
+                send(
+                    &mut writer,
+                    ServerMessage::Error("Already joined".to_string()),
+                )?;
+            }
+            (ClientMessage::Guess(_), None) => {
+                send(
+                    &mut writer,
+                    ServerMessage::Error("Say HELLO first".to_string()),
+                )?;
+            }
+            (ClientMessage::Guess(_), Some(_)) if game_state.is_over() => {
+                send(
+                    &mut writer,
+                    ServerMessage::Error("Game is over".to_string()),
+                )?;
+            }
+            (ClientMessage::Guess(guess), Some(player)) => match game_state.guess(guess) {
+                Ok(won) => {
+                    let last = game_state.guesses.len() - 1;
+                    send(
+                        &mut writer,
+                        ServerMessage::Result(
+                            game_state.guesses[last].clone(),
+                            game_state.get_guess_hits(last),
+                        ),
+                    )?;
+                    let mut race = race.lock().unwrap();
+                    race.broadcast(&ServerMessage::Progress(
+                        player.clone(),
+                        game_state.guesses.len(),
+                    ));
+                    if won && race.solved_by.is_none() {
+                        race.solved_by = Some(player.clone());
+                        race.broadcast(&ServerMessage::Solved(player.clone()));
+                    }
+                    if game_state.is_over() && !won {
+                        send(&mut writer, ServerMessage::Over(game_state.word.clone()))?;
+                    }
+                }
+                Err(error) => send(&mut writer, ServerMessage::Error(error.to_string()))?,
+            },
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,212 @@
+use crate::GameState;
+
+/// Result of a finished game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameRecord {
+    pub won: bool,
+    pub guesses: usize,
+    /// replayed daily puzzle of a past day
+    pub archive: bool,
+}
+
+/// Games played since the program was started.
+#[derive(Debug, Default)]
+pub struct Session {
+    games: Vec<GameRecord>,
+    /// answers of the games in the order they were played
+    answers: Vec<String>,
+}
+
+impl Session {
+    pub fn new() -> Session {
+        Session::default()
+    }
+
+    pub fn record(&mut self, game_state: &GameState) {
+        self.add(GameRecord {
+            won: game_state.won(),
+            guesses: game_state.turns(),
+            archive: game_state.daily.is_some() && !game_state.counts_toward_streak(),
+        });
+        self.answers.push(game_state.word.clone());
+    }
+
+    /// Answers of the last `window` games, new games avoid these.
+    pub fn recent_answers(&self, window: usize) -> &[String] {
+        &self.answers[self.answers.len().saturating_sub(window)..]
+    }
+
+    pub fn add(&mut self, record: GameRecord) {
+        self.games.push(record);
+    }
+
+    pub fn played(&self) -> usize {
+        self.games.len()
+    }
+
+    pub fn won(&self) -> usize {
+        self.games.iter().filter(|game| game.won).count()
+    }
+
+    pub fn archive_games(&self) -> usize {
+        self.games.iter().filter(|game| game.archive).count()
+    }
+
+    /// Longest run of consecutive wins.
+    pub fn best_streak(&self) -> usize {
+        let mut best = 0;
+        let mut current = 0;
+        for game in &self.games {
+            current = if game.won { current + 1 } else { 0 };
+            best = best.max(current);
+        }
+        best
+    }
+
+    /// Number of games lost in a row up to the last one.
+    pub fn loss_streak(&self) -> usize {
+        self.games.iter().rev().take_while(|game| !game.won).count()
+    }
+
+    /// Encouragement after every `threshold` losses in a row, never if it is 0.
+    pub fn cold_streak_message(&self, threshold: usize) -> Option<String> {
+        let losses = self.loss_streak();
+        if threshold == 0 || losses == 0 || losses % threshold != 0 {
+            return None;
+        }
+        Some(format!(
+            "{} losses in a row, tough words! Don't give up, the next one is a fresh start",
+            losses
+        ))
+    }
+
+    /// Average number of guesses needed for the games won.
+    pub fn average_guesses(&self) -> Option<f64> {
+        let won: Vec<&GameRecord> = self.games.iter().filter(|game| game.won).collect();
+        if won.is_empty() {
+            return None;
+        }
+        let total: usize = won.iter().map(|game| game.guesses).sum();
+        Some(total as f64 / won.len() as f64)
+    }
+
+    pub fn summary(&self) -> String {
+        let average = match self.average_guesses() {
+            Some(average) => format!("{:.2}", average),
+            None => "-".to_string(),
+        };
+        let mut summary = format!(
+            "Games played: {}\nGames won: {}\nBest streak: {}\nAverage guesses: {}",
+            self.played(),
+            self.won(),
+            self.best_streak(),
+            average
+        );
+        if self.archive_games() > 0 {
+            summary.push_str(&format!("\nArchive games: {}", self.archive_games()));
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(games: &[(bool, usize)]) -> Session {
+        let mut session = Session::new();
+        for &(won, guesses) in games {
+            session.add(GameRecord {
+                won,
+                guesses,
+                archive: false,
+            });
+        }
+        session
+    }
+
+    #[test]
+    fn test_average_guesses_of_won_games() {
+        let session = session(&[(true, 3), (false, 6), (true, 4), (true, 6)]);
+        assert_eq!(session.played(), 4);
+        assert_eq!(session.won(), 3);
+        assert_eq!(session.average_guesses(), Some(13.0 / 3.0));
+    }
+
+    #[test]
+    fn test_average_guesses_without_wins() {
+        let session = session(&[(false, 6)]);
+        assert_eq!(session.average_guesses(), None);
+        assert!(session.summary().contains("Average guesses: -"));
+    }
+
+    #[test]
+    fn test_archive_games_in_summary() {
+        let mut session = session(&[(true, 3)]);
+        assert!(!session.summary().contains("Archive"));
+        session.add(GameRecord {
+            won: true,
+            guesses: 4,
+            archive: true,
+        });
+        assert_eq!(session.archive_games(), 1);
+        assert!(session.summary().contains("Archive games: 1"));
+    }
+
+    #[test]
+    fn test_recent_answers() {
+        let mut session = Session::new();
+        for word in ["hello", "world", "jolly"] {
+            session.record(&GameState::new(
+                word.to_string(),
+                vec![word.to_string()],
+                false,
+            ));
+        }
+        assert_eq!(session.recent_answers(2), ["world", "jolly"]);
+        assert_eq!(session.recent_answers(10).len(), 3);
+        assert!(session.recent_answers(0).is_empty());
+    }
+
+    #[test]
+    fn test_cold_streak_message() {
+        let mut session = session(&[(false, 6), (true, 4), (false, 6)]);
+        assert_eq!(session.loss_streak(), 1);
+        assert_eq!(session.cold_streak_message(2), None);
+        session.add(GameRecord {
+            won: false,
+            guesses: 6,
+            archive: false,
+        });
+        assert_eq!(session.loss_streak(), 2);
+        assert!(session
+            .cold_streak_message(2)
+            .unwrap()
+            .starts_with("2 losses"));
+        assert_eq!(session.cold_streak_message(3), None);
+        assert_eq!(session.cold_streak_message(0), None);
+        // shown again once the streak doubles, not after every loss
+        for _ in 0..2 {
+            session.add(GameRecord {
+                won: false,
+                guesses: 6,
+                archive: false,
+            });
+        }
+        assert!(session.cold_streak_message(2).is_some());
+        assert!(session.cold_streak_message(3).is_none());
+        session.add(GameRecord {
+            won: true,
+            guesses: 5,
+            archive: false,
+        });
+        assert_eq!(session.loss_streak(), 0);
+        assert_eq!(session.cold_streak_message(1), None);
+    }
+
+    #[test]
+    fn test_best_streak() {
+        let session = session(&[(true, 3), (true, 2), (false, 6), (true, 4)]);
+        assert_eq!(session.best_streak(), 2);
+    }
+}
@@ -0,0 +1,237 @@
+//! Parsing of the emoji grids Wordle and its variants share, like
+//!
+//! ```text
+//! Wordle 1,234 3/6
+//!
+//! ⬛🟨⬛⬛⬛
+//! ⬛🟩🟩⬛🟨
+//! 🟩🟩🟩🟩🟩
+//! ```
+//!
+//! Lines without any tiles, like the title, are skipped. Both the dark and the
+//! light theme are understood, as well as the orange and blue tiles of the high
+//! contrast mode.
+//!
+//! Grids of finished games are written in the same format. They never contain
+//! letters, only the colors of the tiles, so they don't spoil the word.
+
+use crate::protocol::encode_hits;
+use crate::stats::Stats;
+use crate::{row_letters, GameState, HitInfo};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// no row of tiles was found
+    Empty,
+    /// a row of tiles contains something else, lines are counted from 1
+    UnknownSymbol { line: usize, symbol: char },
+    /// a row has a different number of tiles than the first one
+    RowLength { line: usize, expected: usize },
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "No rows of tiles found"),
+            ParseError::UnknownSymbol { line, symbol } => {
+                write!(f, "Unknown symbol '{}' in line {}", symbol, line)
+            }
+            ParseError::RowLength { line, expected } => {
+                write!(f, "Line {} doesn't have {} tiles", line, expected)
+            }
+        }
+    }
+}
+
+fn tile(symbol: char) -> Option<HitInfo> {
+    match symbol {
+        '🟩' | '🟧' => Some(HitInfo::Hit),
+        '🟨' | '🟦' => Some(HitInfo::Contains),
+        '⬛' | '⬜' => Some(HitInfo::Miss),
+        _ => None,
+    }
+}
+
+/// Tile of `hit`, orange and blue replace green and yellow in `high_contrast` grids.
+fn symbol(hit: &HitInfo, high_contrast: bool) -> char {
+    match (hit, high_contrast) {
+        (HitInfo::Hit, false) => '🟩',
+        (HitInfo::Hit, true) => '🟧',
+        (HitInfo::Contains, false) => '🟨',
+        (HitInfo::Contains, true) => '🟦',
+        _ => '⬛',
+    }
+}
+
+/// Row of tiles for `hits`.
+pub fn tiles(hits: &[HitInfo], high_contrast: bool) -> String {
+    hits.iter().map(|hit| symbol(hit, high_contrast)).collect()
+}
+
+/// Share grid of a finished game with a title like `Rustle daily #12 3/6`.
+/// The tiles show the feedback as it was shown while playing.
+pub fn share_grid(game_state: &GameState) -> String {
+    let result = if game_state.won() {
+        game_state.turns().to_string()
+    } else {
+        "X".to_string()
+    };
+    let title = match game_state.game_id() {
+        Some(id) => format!("Rustle {} {}/{}", id, result, game_state.max_tries),
+        None => format!("Rustle {}/{}", result, game_state.max_tries),
+    };
+    let high_contrast = game_state.theme.name == "colorblind";
+    let rows: Vec<String> = (0..game_state.turns())
+        .map(|row| tiles(&row_letters(game_state, row).1, high_contrast))
+        .collect();
+    format!("{}\n\n{}\n", title, rows.join("\n"))
+}
+
+/// Share grid followed by the current streak and the total wins of the player,
+/// for posting. Daily puzzles use the daily streak.
+pub fn streak_post(game_state: &GameState, stats: &Stats) -> String {
+    let streak = match game_state.daily {
+        Some(_) => stats.daily.current,
+        None => stats
+            .for_length(game_state.word.chars().count())
+            .map_or(0, |stats| stats.current_streak),
+    };
+    let wins: u32 = stats.lengths.values().map(|stats| stats.won).sum();
+    format!(
+        "{}\n🔥 Streak: {} | 🏆 Wins: {}\n",
+        share_grid(game_state),
+        streak,
+        wins
+    )
+}
+
+/// Hit patterns of the rows of a share grid.
+pub fn parse_share_grid(grid: &str) -> Result<Vec<Vec<HitInfo>>, ParseError> {
+    let mut rows: Vec<Vec<HitInfo>> = Vec::new();
+    for (i, line) in grid.lines().enumerate() {
+        // emoji may be followed by a variation selector
+        let symbols: Vec<char> = line.trim().chars().filter(|c| *c != '\u{fe0f}').collect();
+        if !symbols.iter().any(|c| tile(*c).is_some()) {
+            continue;
+        }
+        let row = symbols
+            .iter()
+            .map(|c| {
+                tile(*c).ok_or(ParseError::UnknownSymbol {
+                    line: i + 1,
+                    symbol: *c,
+                })
+            })
+            .collect::<Result<Vec<HitInfo>, ParseError>>()?;
+        if let Some(first) = rows.first() {
+            if first.len() != row.len() {
+                return Err(ParseError::RowLength {
+                    line: i + 1,
+                    expected: first.len(),
+                });
+            }
+        }
+        rows.push(row);
+    }
+    if rows.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    Ok(rows)
+}
+
+/// Lists the feedback of every row and how many positions were known after it.
+pub fn analysis(rows: &[Vec<HitInfo>]) -> String {
+    let mut known = vec![false; rows.first().map_or(0, |row| row.len())];
+    let mut text = String::new();
+    for (i, row) in rows.iter().enumerate() {
+        for (position, hit) in row.iter().enumerate() {
+            known[position] |= *hit == HitInfo::Hit;
+        }
+        let misplaced = row.iter().filter(|hit| **hit == HitInfo::Contains).count();
+        text.push_str(&format!(
+            "{} {}  {}/{} positions known, {} misplaced\n",
+            i + 1,
+            encode_hits(row),
+            known.iter().filter(|known| **known).count(),
+            known.len(),
+            misplaced
+        ));
+    }
+    match rows
+        .iter()
+        .position(|row| row.iter().all(|hit| *hit == HitInfo::Hit))
+    {
+        Some(solved) => text.push_str(&format!("Solved in {} guesses\n", solved + 1)),
+        None => text.push_str("Not solved\n"),
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use HitInfo::{Contains, Hit, Miss};
+
+    #[test]
+    fn test_parse_sample_grid() {
+        let grid = "Wordle 1,234 3/6\n\n⬛🟨⬛⬛⬜\n⬛️🟩🟩⬛️🟨\n🟩🟩🟩🟩🟩\n";
+        let rows = parse_share_grid(grid).unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                vec![Miss, Contains, Miss, Miss, Miss],
+                vec![Miss, Hit, Hit, Miss, Contains],
+                vec![Hit; 5],
+            ]
+        );
+        let analysis = analysis(&rows);
+        assert!(analysis.contains("2 .GG.Y  2/5 positions known, 1 misplaced"));
+        assert!(analysis.ends_with("Solved in 3 guesses\n"));
+        // high contrast tiles
+        assert_eq!(
+            parse_share_grid("🟧🟦⬛").unwrap(),
+            vec![vec![Hit, Contains, Miss]]
+        );
+    }
+
+    #[test]
+    fn test_streak_post_contains_streak_and_grid() {
+        let words = vec!["hello".to_string(), "jolly".to_string()];
+        let mut game_state = GameState::new("hello".to_string(), words, false);
+        game_state.guesses = vec!["jolly".to_string(), "hello".to_string()];
+        let mut stats = Stats::default();
+        stats.add(5, true, 3);
+        stats.add(5, false, 6);
+        stats.record(&game_state);
+        stats.add(6, true, 4);
+
+        let grid = share_grid(&game_state);
+        assert_eq!(grid, "Rustle 2/6\n\n⬛🟨🟩🟩⬛\n🟩🟩🟩🟩🟩\n");
+        let post = streak_post(&game_state, &stats);
+        assert!(post.starts_with(&grid));
+        assert!(post.contains("Streak: 1 | 🏆 Wins: 3"));
+        assert!(!post.contains("hello"));
+        // the grid can be read back
+        assert_eq!(parse_share_grid(&post).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_reject_malformed_grids() {
+        assert_eq!(parse_share_grid(""), Err(ParseError::Empty));
+        assert_eq!(parse_share_grid("Wordle 1,234 X/6"), Err(ParseError::Empty));
+        assert_eq!(
+            parse_share_grid("🟩🟩\n🟩x🟩"),
+            Err(ParseError::UnknownSymbol {
+                line: 2,
+                symbol: 'x'
+            })
+        );
+        assert_eq!(
+            parse_share_grid("🟩🟩🟩\n🟩🟩"),
+            Err(ParseError::RowLength {
+                line: 2,
+                expected: 3
+            })
+        );
+    }
+}
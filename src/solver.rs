@@ -0,0 +1,334 @@
+use crate::protocol::encode_hits;
+use crate::{GameState, HitInfo};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// A guess of the solver with what it knew when picking it, for `--solver-log`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Decision {
+    pub answer: String,
+    /// 1 for the first guess
+    pub turn: usize,
+    /// candidates the guess was picked from
+    pub candidates: usize,
+    pub guess: String,
+    /// candidates left in the largest feedback group of the guess
+    pub worst_case: usize,
+    /// G for a hit, Y for a letter in the wrong place and . for a miss
+    pub feedback: String,
+    /// candidates left after the feedback
+    pub remaining: usize,
+}
+
+impl Decision {
+    pub fn new(
+        answer: &str,
+        turn: usize,
+        candidates: &[String],
+        guess: &str,
+        remaining: usize,
+    ) -> Decision {
+        Decision {
+            answer: answer.to_string(),
+            turn,
+            candidates: candidates.len(),
+            guess: guess.to_string(),
+            worst_case: partition(candidates, guess)
+                .first()
+                .map_or(0, |(_, count)| *count),
+            feedback: encode_hits(&GameState::compute_hits(answer, guess)),
+            remaining,
+        }
+    }
+}
+
+/// Writes the decisions to `path`, one JSON object per line.
+pub fn write_log(path: &Path, decisions: &[Decision]) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    for decision in decisions {
+        let line = serde_json::to_string(decision)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        writeln!(file, "{}", line)?;
+    }
+    file.flush()
+}
+
+/// Keeps the candidates which are consistent with `guess` having received `hits`.
+pub fn filter_candidates(candidates: &[String], guess: &str, hits: &[HitInfo]) -> Vec<String> {
+    candidates
+        .iter()
+        .filter(|c| {
+            c.chars().count() == guess.chars().count() && GameState::compute_hits(c, guess) == hits
+        })
+        .cloned()
+        .collect()
+}
+
+/// Wildcard in the patterns of `match_wildcards`.
+pub const WILDCARD: char = '.';
+
+/// Keeps the candidates matching `pattern`, where `.` matches any letter.
+/// A wildcard at a position of a `known` green only matches that letter, a
+/// different letter there matches nothing.
+pub fn match_wildcards(
+    candidates: &[String],
+    pattern: &str,
+    known: &[Option<char>],
+) -> Vec<String> {
+    let pattern: Vec<char> = pattern
+        .chars()
+        .enumerate()
+        .map(|(i, c)| match known.get(i).copied().flatten() {
+            Some(green) if c == WILDCARD => green,
+            _ => c,
+        })
+        .collect();
+    if pattern
+        .iter()
+        .zip(known)
+        .any(|(c, green)| green.is_some_and(|green| green != *c))
+    {
+        return Vec::new();
+    }
+    candidates
+        .iter()
+        .filter(|candidate| {
+            candidate.chars().count() == pattern.len()
+                && candidate
+                    .chars()
+                    .zip(&pattern)
+                    .all(|(c, p)| *p == WILDCARD || c == *p)
+        })
+        .cloned()
+        .collect()
+}
+
+/// Groups the candidates by the feedback `guess` would receive from them.
+/// Returns each pattern with the number of candidates it would leave, largest groups first.
+pub fn partition(candidates: &[String], guess: &str) -> Vec<(Vec<HitInfo>, usize)> {
+    let mut groups: HashMap<Vec<HitInfo>, usize> = HashMap::new();
+    for candidate in candidates {
+        if candidate.chars().count() == guess.chars().count() {
+            *groups
+                .entry(GameState::compute_hits(candidate, guess))
+                .or_insert(0) += 1;
+        }
+    }
+    let mut groups: Vec<(Vec<HitInfo>, usize)> = groups.into_iter().collect();
+    groups.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    groups
+}
+
+/// Expected share of the candidates `guess` eliminates, between 0 and 1.
+/// None without candidates.
+pub fn expected_elimination(candidates: &[String], guess: &str) -> Option<f64> {
+    let groups = partition(candidates, guess);
+    let total: usize = groups.iter().map(|(_, count)| count).sum();
+    if total == 0 {
+        return None;
+    }
+    // a candidate is the answer with the same chance as any other, leaving its group
+    let remaining: usize = groups.iter().map(|(_, count)| count * count).sum();
+    Some(1.0 - remaining as f64 / (total * total) as f64)
+}
+
+/// Candidate which leaves the fewest candidates in the worst case.
+pub fn best_guess(candidates: &[String]) -> Option<&String> {
+    candidates.iter().min_by_key(|guess| {
+        partition(candidates, guess)
+            .first()
+            .map(|(_, count)| *count)
+            .unwrap_or(0)
+    })
+}
+
+/// Plays `answer` by always picking the best guess, starting with `opener`.
+/// Returns the number of guesses needed, or None if it wasn't solved in `max_tries`.
+/// Every guess is added to `log` if one is given.
+pub fn simulate(
+    answer: &str,
+    candidates: &[String],
+    opener: &str,
+    max_tries: usize,
+    mut log: Option<&mut Vec<Decision>>,
+) -> Option<usize> {
+    let mut candidates = candidates.to_vec();
+    let mut guess = opener.to_string();
+    for tries in 1..=max_tries {
+        let remaining = filter_candidates(
+            &candidates,
+            &guess,
+            &GameState::compute_hits(answer, &guess),
+        );
+        if let Some(log) = log.as_deref_mut() {
+            log.push(Decision::new(
+                answer,
+                tries,
+                &candidates,
+                &guess,
+                remaining.len(),
+            ));
+        }
+        if guess == answer {
+            return Some(tries);
+        }
+        candidates = remaining;
+        guess = best_guess(&candidates)?.clone();
+    }
+    None
+}
+
+/// Guesses the solver plays to find `answer`, opening with the best guess of the candidates.
+/// None if it isn't solved in `max_tries`.
+pub fn optimal_line(answer: &str, candidates: &[String], max_tries: usize) -> Option<Vec<String>> {
+    let mut candidates = candidates.to_vec();
+    if !candidates.iter().any(|c| c == answer) {
+        candidates.push(answer.to_string());
+    }
+    let opener = best_guess(&candidates)?.clone();
+    let mut log = Vec::new();
+    simulate(answer, &candidates, &opener, max_tries, Some(&mut log))?;
+    Some(log.into_iter().map(|decision| decision.guess).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn test_filter_candidates() {
+        let candidates = words(&["hello", "jolly", "world", "hells"]);
+        let hits = GameState::compute_hits("hello", "hells");
+        let filtered = filter_candidates(&candidates, "hells", &hits);
+        assert_eq!(filtered, words(&["hello"]));
+    }
+
+    #[test]
+    fn test_expected_elimination() {
+        let candidates = words(&["hello", "jolly", "holly", "world"]);
+        // every candidate gets a different pattern from "holly"
+        assert_eq!(expected_elimination(&candidates, "holly"), Some(0.75));
+        // "cabin" shares no letter with any of them
+        assert_eq!(expected_elimination(&candidates, "cabin"), Some(0.0));
+        let quality = expected_elimination(&candidates, "cello").unwrap();
+        assert!(quality > 0.0 && quality < 0.75, "{}", quality);
+        assert_eq!(expected_elimination(&[], "hello"), None);
+    }
+
+    #[test]
+    fn test_partition() {
+        let candidates = words(&["hello", "jolly", "holly", "world"]);
+        let groups = partition(&candidates, "holly");
+        assert_eq!(groups.len(), 4);
+        assert_eq!(groups.iter().map(|(_, count)| count).sum::<usize>(), 4);
+
+        let candidates = words(&["aaaab", "aaaac", "aaaad", "bbbbb"]);
+        let groups = partition(&candidates, "aaaax");
+        assert_eq!(groups.len(), 2);
+        assert_eq!(
+            groups[0],
+            (
+                vec![
+                    HitInfo::Hit,
+                    HitInfo::Hit,
+                    HitInfo::Hit,
+                    HitInfo::Hit,
+                    HitInfo::Miss
+                ],
+                3
+            )
+        );
+        assert_eq!(groups[1], (vec![HitInfo::Miss; 5], 1));
+    }
+
+    #[test]
+    fn test_best_guess_splits_candidates() {
+        let candidates = words(&["aaaab", "aaaac", "aaaad", "abcde"]);
+        assert_eq!(best_guess(&candidates), Some(&"abcde".to_string()));
+        assert_eq!(best_guess(&[]), None);
+    }
+
+    #[test]
+    fn test_simulate() {
+        let candidates = words(&["hello", "jolly", "world"]);
+        assert_eq!(simulate("hello", &candidates, "hello", 6, None), Some(1));
+        assert_eq!(simulate("world", &candidates, "hello", 6, None), Some(2));
+        assert_eq!(simulate("world", &candidates, "hello", 1, None), None);
+    }
+
+    #[test]
+    fn test_optimal_line_solves_within_max_tries() {
+        let candidates = words(&["hello", "jolly", "world", "belly", "holly", "wordy"]);
+        for answer in &candidates {
+            let line = optimal_line(answer, &candidates, 6).unwrap();
+            assert!(line.len() <= 6);
+            assert_eq!(line.last(), Some(answer));
+        }
+        // an answer missing from the candidates is still found
+        let line = optimal_line("dolly", &candidates, 6).unwrap();
+        assert_eq!(line.last().map(String::as_str), Some("dolly"));
+        assert_eq!(optimal_line("world", &candidates, 0), None);
+    }
+
+    #[test]
+    fn test_match_wildcards() {
+        let candidates = words(&["crane", "crate", "chase", "grace", "caste", "cra"]);
+        assert_eq!(
+            match_wildcards(&candidates, "c.a.e", &[]),
+            words(&["crane", "crate", "chase"])
+        );
+        assert_eq!(match_wildcards(&candidates, ".....", &[]).len(), 5);
+        assert!(match_wildcards(&candidates, "x....", &[]).is_empty());
+        // a known green fills its wildcard
+        let known = [None, Some('r'), None, None, None];
+        assert_eq!(
+            match_wildcards(&candidates, "c.a.e", &known),
+            words(&["crane", "crate"])
+        );
+        // and rules out a different letter
+        assert!(match_wildcards(&candidates, "cha.e", &known).is_empty());
+    }
+
+    #[test]
+    fn test_log_has_one_decision_per_guess() {
+        let candidates = words(&["hello", "jolly", "world", "belly"]);
+        let mut log = Vec::new();
+        let guesses = simulate("world", &candidates, "hello", 6, Some(&mut log)).unwrap();
+        assert_eq!(log.len(), guesses);
+        assert_eq!(log[0].guess, "hello");
+        assert_eq!(log[0].candidates, 4);
+        assert_eq!(
+            log[0].feedback,
+            encode_hits(&GameState::compute_hits("world", "hello"))
+        );
+        let last = log.last().unwrap();
+        assert_eq!(last.guess, "world");
+        assert_eq!(last.feedback, "GGGGG");
+        assert_eq!(last.remaining, 1);
+        assert!(log
+            .windows(2)
+            .all(|pair| pair[1].candidates == pair[0].remaining));
+
+        let path = std::env::temp_dir().join(format!("rustle-solver-{}.jsonl", std::process::id()));
+        write_log(&path, &log).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(content.lines().count(), guesses);
+        assert!(content.starts_with(r#"{"answer":"world","turn":1,"candidates":4,"guess":"hello""#));
+    }
+
+    #[test]
+    fn test_filter_candidates_skips_wrong_length() {
+        let candidates = words(&["hello", "help"]);
+        let hits = vec![HitInfo::Hit, HitInfo::Hit, HitInfo::Hit, HitInfo::Miss];
+        let filtered = filter_candidates(&candidates, "helm", &hits);
+        assert_eq!(filtered, words(&["help"]));
+    }
+}
@@ -0,0 +1,492 @@
+use crate::GameState;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Statistics of the games played with one word length.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LengthStats {
+    pub played: u32,
+    pub won: u32,
+    pub current_streak: u32,
+    pub max_streak: u32,
+    /// number of games won with 1, 2, 3, ... guesses
+    pub distribution: Vec<u32>,
+}
+
+impl LengthStats {
+    pub fn add(&mut self, won: bool, guesses: usize) {
+        self.played += 1;
+        if won {
+            self.won += 1;
+            self.current_streak += 1;
+            self.max_streak = self.max_streak.max(self.current_streak);
+            if guesses > 0 {
+                if self.distribution.len() < guesses {
+                    self.distribution.resize(guesses, 0);
+                }
+                self.distribution[guesses - 1] += 1;
+            }
+        } else {
+            self.current_streak = 0;
+        }
+    }
+
+    pub fn win_percentage(&self) -> u32 {
+        (self.won * 100).checked_div(self.played).unwrap_or(0)
+    }
+
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "Played: {}\nWon: {} ({}%)\nCurrent streak: {}\nMax streak: {}\nGuess distribution:",
+            self.played,
+            self.won,
+            self.win_percentage(),
+            self.current_streak,
+            self.max_streak
+        );
+        for (i, count) in self.distribution.iter().enumerate() {
+            summary.push_str(&format!(
+                "\n{} {} {}",
+                i + 1,
+                "#".repeat(*count as usize),
+                count
+            ));
+        }
+        summary
+    }
+}
+
+/// Consecutive days on which the daily puzzle was solved.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyStreak {
+    pub last_index: Option<usize>,
+    pub current: u32,
+    pub max: u32,
+    /// streak freezes available, each protects the streak for a single missed day
+    pub freezes: u32,
+}
+
+/// A streak freeze is earned every this many days in a row.
+pub const FREEZE_EVERY: u32 = 7;
+pub const MAX_FREEZES: u32 = 2;
+
+impl DailyStreak {
+    pub fn add(&mut self, index: usize, won: bool) {
+        if !won {
+            self.current = 0;
+        } else if self.last_index != Some(index) {
+            let previous = self.last_index.map(|last| index.saturating_sub(last));
+            let continues = match previous {
+                Some(1) => true,
+                Some(2) if self.freezes > 0 => {
+                    self.freezes -= 1;
+                    true
+                }
+                _ => false,
+            };
+            self.current = if continues { self.current + 1 } else { 1 };
+            self.max = self.max.max(self.current);
+            if self.current % FREEZE_EVERY == 0 {
+                self.freezes = (self.freezes + 1).min(MAX_FREEZES);
+            }
+        }
+        self.last_index = Some(index);
+    }
+}
+
+/// Games started from a challenge code, kept out of the other statistics.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChallengeStats {
+    pub played: u32,
+    pub won: u32,
+}
+
+/// Version of the stats file written by this build.
+pub const STATS_VERSION: u64 = 5;
+
+/// Persisted statistics, kept separately for every word length.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+    pub version: u64,
+    pub lengths: BTreeMap<usize, LengthStats>,
+    pub daily: DailyStreak,
+    pub challenges: ChallengeStats,
+    /// games in which a hint was revealed
+    pub hinted: u32,
+}
+
+impl Default for Stats {
+    fn default() -> Stats {
+        Stats {
+            version: STATS_VERSION,
+            lengths: BTreeMap::new(),
+            daily: DailyStreak::default(),
+            challenges: ChallengeStats::default(),
+            hinted: 0,
+        }
+    }
+}
+
+/// Upgrades the content of an older stats file to the current version.
+///
+/// - version 1 had no version field, later builds of it added the daily streak
+/// - version 2 had no streak freezes
+/// - version 3 had no challenge statistics
+/// - version 4 didn't count games with hints
+pub fn migrate(mut old: Value) -> serde_json::Result<Stats> {
+    let version = old.get("version").and_then(Value::as_u64).unwrap_or(1);
+    if let Some(fields) = old.as_object_mut() {
+        if version < 2 {
+            fields
+                .entry("daily")
+                .or_insert(serde_json::to_value(DailyStreak::default())?);
+        }
+        if version < 3 {
+            if let Some(daily) = fields.get_mut("daily").and_then(Value::as_object_mut) {
+                daily.entry("freezes").or_insert(0.into());
+            }
+        }
+        if version < 4 {
+            fields.insert(
+                "challenges".to_string(),
+                serde_json::to_value(ChallengeStats::default())?,
+            );
+        }
+        if version < 5 {
+            fields.insert("hinted".to_string(), 0.into());
+        }
+        fields.insert("version".to_string(), STATS_VERSION.into());
+    }
+    serde_json::from_value(old)
+}
+
+impl Stats {
+    pub fn record(&mut self, game_state: &GameState) {
+        if game_state.challenge.is_some() {
+            self.challenges.played += 1;
+            if game_state.won() {
+                self.challenges.won += 1;
+            }
+            return;
+        }
+        self.add(
+            game_state.word.chars().count(),
+            game_state.won(),
+            game_state.turns(),
+        );
+        if !game_state.hints.is_empty() {
+            self.hinted += 1;
+        }
+        if let Some(daily) = game_state
+            .daily
+            .filter(|_| game_state.counts_toward_streak())
+        {
+            self.daily.add(daily.index, game_state.won());
+        }
+    }
+
+    pub fn played(&self) -> u32 {
+        self.lengths.values().map(|stats| stats.played).sum()
+    }
+
+    pub fn add(&mut self, length: usize, won: bool, guesses: usize) {
+        self.lengths.entry(length).or_default().add(won, guesses);
+    }
+
+    pub fn for_length(&self, length: usize) -> Option<&LengthStats> {
+        self.lengths.get(&length)
+    }
+
+    /// Statistics of `length`, or of all lengths played if None.
+    pub fn summary(&self, length: Option<usize>) -> String {
+        let lengths: Vec<(usize, &LengthStats)> = match length {
+            Some(length) => self
+                .for_length(length)
+                .map(|stats| (length, stats))
+                .into_iter()
+                .collect(),
+            None => self
+                .lengths
+                .iter()
+                .map(|(length, stats)| (*length, stats))
+                .collect(),
+        };
+        if lengths.is_empty() && self.challenges.played == 0 {
+            return "No games played yet".to_string();
+        }
+        let mut summary = lengths
+            .iter()
+            .map(|(length, stats)| format!("{} letters\n{}", length, stats.summary()))
+            .collect::<Vec<String>>()
+            .join("\n\n");
+        if self.daily.last_index.is_some() {
+            summary.push_str(&format!(
+                "\n\nDaily streak: {} (max {})\nStreak freezes: {}",
+                self.daily.current, self.daily.max, self.daily.freezes
+            ));
+        }
+        if self.hinted > 0 {
+            summary.push_str(&format!("\n\nGames with a hint: {}", self.hinted));
+        }
+        if self.challenges.played > 0 {
+            summary.push_str(&format!(
+                "\n\nChallenges won: {}/{}",
+                self.challenges.won, self.challenges.played
+            ));
+        }
+        summary.trim_start().to_string()
+    }
+
+    /// Loads the stats, a missing file means nothing was played yet.
+    pub fn load(path: &Path) -> io::Result<Stats> {
+        match fs::read_to_string(path) {
+            Ok(content) => {
+                let invalid = |error| io::Error::new(io::ErrorKind::InvalidData, error);
+                let value: Value = serde_json::from_str(&content).map_err(invalid)?;
+                match value.get("version").and_then(Value::as_u64) {
+                    Some(version) if version > STATS_VERSION => Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("stats file version {} is not supported", version),
+                    )),
+                    _ => migrate(value).map_err(invalid),
+                }
+            }
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Stats::default()),
+            Err(error) => Err(error),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        fs::write(path, content)
+    }
+}
+
+/// Directory for persisted data, `$XDG_DATA_HOME/rustle` or `~/.local/share/rustle`.
+pub fn data_dir() -> Option<PathBuf> {
+    match std::env::var_os("XDG_DATA_HOME") {
+        Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir).join("rustle")),
+        _ => std::env::var_os("HOME").map(|home| {
+            PathBuf::from(home)
+                .join(".local")
+                .join("share")
+                .join("rustle")
+        }),
+    }
+}
+
+pub fn stats_path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("stats.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lengths_are_tracked_separately() {
+        let mut stats = Stats::default();
+        stats.add(5, true, 3);
+        stats.add(5, true, 4);
+        stats.add(6, false, 6);
+        let five = stats.for_length(5).unwrap();
+        assert_eq!(five.played, 2);
+        assert_eq!(five.current_streak, 2);
+        assert_eq!(five.distribution, vec![0, 0, 1, 1]);
+        let six = stats.for_length(6).unwrap();
+        assert_eq!(six.played, 1);
+        assert_eq!(six.won, 0);
+        assert!(six.distribution.is_empty());
+        assert_eq!(stats.for_length(7), None);
+    }
+
+    #[test]
+    fn test_loss_resets_streak() {
+        let mut stats = LengthStats::default();
+        stats.add(true, 2);
+        stats.add(true, 2);
+        stats.add(false, 6);
+        stats.add(true, 5);
+        assert_eq!(stats.current_streak, 1);
+        assert_eq!(stats.max_streak, 2);
+        assert_eq!(stats.win_percentage(), 75);
+    }
+
+    #[test]
+    fn test_migrate_version_1() {
+        let old = serde_json::json!({
+            "lengths": {
+                "5": {
+                    "played": 3,
+                    "won": 2,
+                    "current_streak": 1,
+                    "max_streak": 1,
+                    "distribution": [0, 1, 1]
+                }
+            }
+        });
+        let stats = migrate(old).unwrap();
+        assert_eq!(stats.version, STATS_VERSION);
+        assert_eq!(stats.daily, DailyStreak::default());
+        let five = stats.for_length(5).unwrap();
+        assert_eq!(five.played, 3);
+        assert_eq!(five.distribution, vec![0, 1, 1]);
+    }
+
+    #[test]
+    fn test_migrate_version_1_keeps_daily_streak() {
+        let old = serde_json::json!({
+            "lengths": {},
+            "daily": {"last_index": 12, "current": 3, "max": 5}
+        });
+        let stats = migrate(old).unwrap();
+        assert_eq!(stats.daily.last_index, Some(12));
+        assert_eq!(stats.daily.current, 3);
+        assert_eq!(stats.daily.max, 5);
+        assert_eq!(stats.daily.freezes, 0);
+    }
+
+    #[test]
+    fn test_migrate_version_2() {
+        let old = serde_json::json!({
+            "version": 2,
+            "lengths": {},
+            "daily": {"last_index": 12, "current": 3, "max": 5}
+        });
+        let stats = migrate(old).unwrap();
+        assert_eq!(stats.daily.current, 3);
+        assert_eq!(stats.daily.freezes, 0);
+        assert_eq!(stats.challenges, ChallengeStats::default());
+        assert_eq!(stats.hinted, 0);
+    }
+
+    #[test]
+    fn test_hinted_games_are_counted() {
+        let words = vec!["hello".to_string(), "jolly".to_string()];
+        let mut game_state = GameState::new("hello".to_string(), words, false);
+        game_state.guesses = vec!["jolly".to_string(), "hello".to_string()];
+        let mut stats = Stats::default();
+        stats.record(&game_state);
+        game_state.hints = vec![0];
+        stats.record(&game_state);
+        assert_eq!(stats.played(), 2);
+        assert_eq!(stats.hinted, 1);
+        assert!(stats.summary(None).contains("Games with a hint: 1"));
+    }
+
+    #[test]
+    fn test_challenges_are_kept_apart() {
+        let mut game_state = GameState::new("hello".to_string(), vec!["hello".to_string()], false);
+        game_state.guesses.push("hello".to_string());
+        game_state.challenge = Some(crate::challenge::ChallengeSpec {
+            puzzle: crate::challenge::Puzzle::Answer(0),
+            length: 5,
+            guesses: None,
+        });
+        let mut stats = Stats::default();
+        stats.record(&game_state);
+        assert_eq!(stats.challenges, ChallengeStats { played: 1, won: 1 });
+        assert_eq!(stats.played(), 0);
+        assert_eq!(stats.summary(None), "Challenges won: 1/1");
+    }
+
+    #[test]
+    fn test_current_version_is_unchanged() {
+        let mut stats = Stats::default();
+        stats.add(5, true, 2);
+        stats.daily.add(3, true);
+        let value = serde_json::to_value(&stats).unwrap();
+        assert_eq!(migrate(value).unwrap(), stats);
+    }
+
+    #[test]
+    fn test_future_version_is_rejected() {
+        let path =
+            std::env::temp_dir().join(format!("rustle-stats-v9-{}.json", std::process::id()));
+        std::fs::write(&path, r#"{"version": 9, "something": "new"}"#).unwrap();
+        let error = Stats::load(&path).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.to_string().contains("version 9"));
+    }
+
+    #[test]
+    fn test_daily_streak() {
+        let mut streak = DailyStreak::default();
+        streak.add(10, true);
+        streak.add(11, true);
+        streak.add(11, true);
+        assert_eq!(streak.current, 2);
+        streak.add(13, true);
+        assert_eq!(streak.current, 1);
+        streak.add(14, false);
+        assert_eq!(streak.current, 0);
+        assert_eq!(streak.max, 2);
+    }
+
+    fn streak(days: std::ops::Range<usize>) -> DailyStreak {
+        let mut streak = DailyStreak::default();
+        for day in days {
+            streak.add(day, true);
+        }
+        streak
+    }
+
+    #[test]
+    fn test_freeze_is_earned_and_consumed() {
+        let mut streak = streak(0..7);
+        assert_eq!(streak.freezes, 1);
+        // day 7 is missed
+        streak.add(8, true);
+        assert_eq!(streak.current, 8);
+        assert_eq!(streak.freezes, 0);
+    }
+
+    #[test]
+    fn test_missed_day_without_freeze_resets() {
+        let mut streak = streak(0..5);
+        assert_eq!(streak.freezes, 0);
+        streak.add(6, true);
+        assert_eq!(streak.current, 1);
+    }
+
+    #[test]
+    fn test_freeze_covers_only_one_day() {
+        let mut streak = streak(0..7);
+        streak.add(9, true);
+        assert_eq!(streak.current, 1);
+        assert_eq!(streak.freezes, 1);
+    }
+
+    #[test]
+    fn test_summary_of_single_length() {
+        let mut stats = Stats::default();
+        stats.add(5, true, 3);
+        stats.add(6, true, 4);
+        let summary = stats.summary(Some(6));
+        assert!(summary.starts_with("6 letters"));
+        assert!(!summary.contains("5 letters"));
+        assert!(stats.summary(None).contains("5 letters"));
+        assert_eq!(stats.summary(Some(7)), "No games played yet");
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir().join(format!("rustle-stats-{}.json", std::process::id()));
+        let mut stats = Stats::default();
+        stats.add(5, true, 3);
+        stats.add(6, false, 6);
+        stats.save(&path).unwrap();
+        assert_eq!(Stats::load(&path).unwrap(), stats);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(Stats::load(&path).unwrap(), Stats::default());
+    }
+}
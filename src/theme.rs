@@ -0,0 +1,181 @@
+use crate::HitInfo;
+use termion::{color, style};
+
+/// Names of the themes in the order they are cycled through.
+pub const THEMES: [&str; 4] = ["dark", "colorblind", "light", "no-color"];
+
+/// Foreground and background escape codes of a tile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Style {
+    pub fg: String,
+    pub bg: String,
+}
+
+impl Style {
+    pub fn new<F: color::Color, B: color::Color>(fg: F, bg: B) -> Style {
+        Style {
+            fg: color::Fg(fg).to_string(),
+            bg: color::Bg(bg).to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for Style {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}{}", self.bg, self.fg)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    pub name: &'static str,
+    pub hit: Style,
+    pub contains: Style,
+    pub miss: Style,
+    pub empty: Style,
+    /// error messages below the board
+    pub error: Style,
+}
+
+impl Theme {
+    /// Colors for terminals with a dark background.
+    pub fn dark() -> Theme {
+        Theme {
+            name: "dark",
+            hit: Style::new(color::Black, color::Green),
+            contains: Style::new(color::Black, color::Yellow),
+            miss: Style::new(color::White, color::LightBlack),
+            empty: Style::new(color::Reset, color::Reset),
+            error: Style::new(color::LightRed, color::Reset),
+        }
+    }
+
+    /// Colors for terminals with a light background.
+    pub fn light() -> Theme {
+        Theme {
+            name: "light",
+            hit: Style::new(color::White, color::Green),
+            contains: Style::new(color::Black, color::Yellow),
+            miss: Style::new(color::White, color::LightBlack),
+            empty: Style::new(color::Reset, color::Reset),
+            error: Style::new(color::Red, color::Reset),
+        }
+    }
+
+    /// Orange and blue instead of green and yellow, for red-green color blindness.
+    pub fn colorblind() -> Theme {
+        Theme {
+            name: "colorblind",
+            hit: Style::new(color::Black, color::Rgb(245, 121, 58)),
+            contains: Style::new(color::Black, color::Rgb(133, 192, 249)),
+            miss: Style::new(color::White, color::LightBlack),
+            empty: Style::new(color::Reset, color::Reset),
+            error: Style::new(color::LightRed, color::Reset),
+        }
+    }
+
+    /// Text attributes instead of colors: inverted hits, underlined present letters and faint
+    /// misses.
+    pub fn no_color() -> Theme {
+        let attributes = |attributes: &[&dyn std::fmt::Display]| Style {
+            fg: attributes.iter().map(|a| a.to_string()).collect(),
+            bg: String::new(),
+        };
+        Theme {
+            name: "no-color",
+            hit: attributes(&[&style::Bold, &style::Invert]),
+            contains: attributes(&[&style::Underline]),
+            miss: attributes(&[&style::Faint]),
+            empty: attributes(&[]),
+            error: attributes(&[&style::Bold]),
+        }
+    }
+
+    /// The theme following this one in `THEMES`, wrapping around after the last.
+    pub fn next(&self) -> Theme {
+        let i = THEMES
+            .iter()
+            .position(|name| *name == self.name)
+            .unwrap_or(0);
+        Theme::from_name(THEMES[(i + 1) % THEMES.len()]).unwrap()
+    }
+
+    pub fn style(&self, hit: &HitInfo) -> &Style {
+        match hit {
+            HitInfo::Hit => &self.hit,
+            HitInfo::Contains => &self.contains,
+            HitInfo::Miss => &self.miss,
+            HitInfo::None => &self.empty,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Theme> {
+        match name {
+            "dark" => Some(Theme::dark()),
+            "colorblind" => Some(Theme::colorblind()),
+            "light" => Some(Theme::light()),
+            "no-color" => Some(Theme::no_color()),
+            _ => None,
+        }
+    }
+
+    /// Picks a theme matching the terminal background given in `COLORFGBG`.
+    pub fn detect() -> Theme {
+        match std::env::var("COLORFGBG") {
+            Ok(value) if is_light_background(&value) => Theme::light(),
+            _ => Theme::dark(),
+        }
+    }
+}
+
+/// `COLORFGBG` looks like "15;0", the last number is the background color.
+fn is_light_background(colorfgbg: &str) -> bool {
+    match colorfgbg.rsplit(';').next().map(|bg| bg.parse::<u8>()) {
+        Some(Ok(bg)) => bg == 7 || bg >= 9,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_miss_tiles_have_foreground() {
+        for theme in [Theme::dark(), Theme::light()] {
+            assert!(!theme.miss.fg.is_empty(), "{} has no miss fg", theme.name);
+            assert!(!theme.miss.bg.is_empty(), "{} has no miss bg", theme.name);
+            assert_ne!(theme.miss.fg, color::Fg(color::Reset).to_string());
+        }
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(Theme::from_name("light"), Some(Theme::light()));
+        assert_eq!(Theme::from_name("dark"), Some(Theme::dark()));
+        assert_eq!(Theme::from_name("neon"), None);
+    }
+
+    #[test]
+    fn test_next_cycles_through_all_themes() {
+        let mut theme = Theme::dark();
+        let mut names = Vec::new();
+        for _ in 0..THEMES.len() {
+            names.push(theme.name);
+            theme = theme.next();
+        }
+        assert_eq!(names, THEMES);
+        assert_eq!(theme, Theme::dark());
+        for name in THEMES {
+            assert_eq!(Theme::from_name(name).unwrap().name, name);
+        }
+    }
+
+    #[test]
+    fn test_is_light_background() {
+        assert!(is_light_background("0;15"));
+        assert!(is_light_background("0;default;7"));
+        assert!(!is_light_background("15;0"));
+        assert!(!is_light_background("garbage"));
+    }
+}
@@ -0,0 +1,159 @@
+use std::fs::File;
+use std::os::unix::io::AsRawFd;
+use std::time::{Duration, Instant};
+
+/// Clock of a timed game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timer {
+    started: Instant,
+    turn_started: Instant,
+    paused_since: Option<Instant>,
+    /// total time spent in finished pauses
+    paused: Duration,
+    /// time for a guess before the turn is forfeited
+    pub turn_limit: Option<Duration>,
+}
+
+impl Timer {
+    pub fn new(now: Instant, turn_limit: Option<Duration>) -> Timer {
+        Timer {
+            started: now,
+            turn_started: now,
+            paused_since: None,
+            paused: Duration::ZERO,
+            turn_limit,
+        }
+    }
+
+    /// The clock stands still while paused.
+    fn clock(&self, now: Instant) -> Instant {
+        self.paused_since.unwrap_or(now)
+    }
+
+    /// Time played, pauses excluded.
+    pub fn elapsed(&self, now: Instant) -> Duration {
+        self.clock(now)
+            .saturating_duration_since(self.started)
+            .saturating_sub(self.paused)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused_since.is_some()
+    }
+
+    pub fn pause(&mut self, now: Instant) {
+        if self.paused_since.is_none() {
+            self.paused_since = Some(now);
+        }
+    }
+
+    pub fn resume(&mut self, now: Instant) {
+        if let Some(since) = self.paused_since.take() {
+            let pause = now.saturating_duration_since(since);
+            self.paused += pause;
+            // the pause doesn't count towards the turn limit either
+            self.turn_started += pause;
+        }
+    }
+
+    /// Time left for the current guess, None without a turn limit.
+    pub fn turn_remaining(&self, now: Instant) -> Option<Duration> {
+        self.turn_limit.map(|limit| {
+            limit.saturating_sub(self.clock(now).saturating_duration_since(self.turn_started))
+        })
+    }
+
+    pub fn turn_expired(&self, now: Instant) -> bool {
+        self.turn_remaining(now) == Some(Duration::ZERO)
+    }
+
+    /// Starts the next turn and returns how long the finished one took, pauses excluded.
+    pub fn next_turn(&mut self, now: Instant) -> Duration {
+        let taken = self.clock(now).saturating_duration_since(self.turn_started);
+        self.turn_started = now;
+        taken
+    }
+}
+
+/// Formats a duration as minutes and seconds, e.g. "1:05".
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// Waits up to `timeout` for input on `tty`, returns true if there is some.
+pub fn wait_for_input(tty: &File, timeout: Duration) -> bool {
+    let mut fds = libc::pollfd {
+        fd: tty.as_raw_fd(),
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    // SAFETY: `fds` points to a single valid pollfd for the duration of the call
+    unsafe { libc::poll(&mut fds, 1, timeout.as_millis() as libc::c_int) > 0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turn_limit() {
+        let start = Instant::now();
+        let mut timer = Timer::new(start, Some(Duration::from_secs(10)));
+        let later = start + Duration::from_secs(4);
+        assert_eq!(timer.turn_remaining(later), Some(Duration::from_secs(6)));
+        assert!(!timer.turn_expired(later));
+        assert!(timer.turn_expired(start + Duration::from_secs(10)));
+        timer.next_turn(later);
+        assert!(!timer.turn_expired(start + Duration::from_secs(10)));
+        assert_eq!(timer.elapsed(later), Duration::from_secs(4));
+    }
+
+    #[test]
+    fn test_elapsed_excludes_pauses() {
+        let start = Instant::now();
+        let at = |seconds| start + Duration::from_secs(seconds);
+        let mut timer = Timer::new(start, Some(Duration::from_secs(10)));
+        timer.pause(at(5));
+        assert!(timer.is_paused());
+        assert_eq!(timer.elapsed(at(20)), Duration::from_secs(5));
+        assert!(!timer.turn_expired(at(20)));
+        timer.resume(at(30));
+        assert!(!timer.is_paused());
+        assert_eq!(timer.elapsed(at(32)), Duration::from_secs(7));
+        assert_eq!(timer.turn_remaining(at(32)), Some(Duration::from_secs(3)));
+        timer.pause(at(40));
+        timer.resume(at(50));
+        assert_eq!(timer.elapsed(at(50)), Duration::from_secs(15));
+    }
+
+    #[test]
+    fn test_turn_durations() {
+        let start = Instant::now();
+        let at = |seconds| start + Duration::from_secs(seconds);
+        let mut timer = Timer::new(start, None);
+        assert_eq!(timer.next_turn(at(4)), Duration::from_secs(4));
+        timer.pause(at(6));
+        timer.resume(at(16));
+        assert_eq!(timer.next_turn(at(19)), Duration::from_secs(5));
+        assert_eq!(timer.next_turn(at(20)), Duration::from_secs(1));
+        assert_eq!(timer.elapsed(at(20)), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_without_turn_limit() {
+        let start = Instant::now();
+        let timer = Timer::new(start, None);
+        assert_eq!(
+            timer.turn_remaining(start + Duration::from_secs(3600)),
+            None
+        );
+        assert!(!timer.turn_expired(start + Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(65)), "1:05");
+        assert_eq!(format_duration(Duration::from_millis(999)), "0:00");
+    }
+}
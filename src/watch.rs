@@ -0,0 +1,228 @@
+use crate::error::RustleError;
+use crate::solver::{self, Decision};
+use crate::{animate_reveal, render, timer, GameState, BOARD_LEFT};
+use std::io::{stdout, Write};
+use std::time::Duration;
+use termion::event::Key;
+use termion::input::TermRead;
+use termion::raw::IntoRawMode;
+
+const MIN_DELAY: Duration = Duration::from_millis(125);
+const MAX_DELAY: Duration = Duration::from_millis(8000);
+
+/// What the playback does after a key press.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    Continue,
+    /// make the next guess right away
+    Step,
+    Stop,
+}
+
+/// Speed and pause state of watching the solver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Playback {
+    pub delay: Duration,
+    pub paused: bool,
+}
+
+impl Playback {
+    pub fn new(delay: Duration) -> Playback {
+        Playback {
+            delay: delay.clamp(MIN_DELAY, MAX_DELAY),
+            paused: false,
+        }
+    }
+
+    pub fn faster(&mut self) {
+        self.delay = (self.delay / 2).max(MIN_DELAY);
+    }
+
+    pub fn slower(&mut self) {
+        self.delay = (self.delay * 2).min(MAX_DELAY);
+    }
+
+    /// `+` and `-` change the speed, space pauses, `n` steps and `q` or Esc stops.
+    pub fn handle(&mut self, key: Key) -> Control {
+        match key {
+            Key::Char('+') => self.faster(),
+            Key::Char('-') => self.slower(),
+            Key::Char(' ') => self.paused = !self.paused,
+            Key::Char('n') => return Control::Step,
+            Key::Char('q') | Key::Esc | Key::Ctrl('c') => return Control::Stop,
+            _ => (),
+        }
+        Control::Continue
+    }
+
+    pub fn status(&self) -> String {
+        format!(
+            "{} ms per guess{}  (+/- speed, space pause, n step, q stop)",
+            self.delay.as_millis(),
+            if self.paused { ", paused" } else { "" }
+        )
+    }
+}
+
+/// Bar of `width` cells filled by the solver's confidence, one over the number of
+/// remaining candidates. It is empty while every candidate is equally likely to
+/// be the answer and full once one candidate is left.
+pub fn confidence_bar(remaining: usize, width: usize) -> String {
+    let filled = match remaining {
+        0 => 0,
+        remaining => (width as f64 / remaining as f64).round() as usize,
+    };
+    format!("[{}{}]", "#".repeat(filled), ".".repeat(width - filled))
+}
+
+/// Lets the solver make the next guess, returns false if it has no candidate left.
+pub fn step(game_state: &mut GameState, log: Option<&mut Vec<Decision>>) -> bool {
+    let candidates = game_state.remaining_candidates();
+    let guess = match solver::best_guess(&candidates) {
+        Some(guess) => guess.clone(),
+        None => return false,
+    };
+    game_state.current_guess = guess.clone();
+    game_state.confirm();
+    if let (Some(log), None) = (log, game_state.last_error) {
+        log.push(Decision::new(
+            &game_state.word,
+            game_state.guesses.len(),
+            &candidates,
+            &guess,
+            game_state.remaining_candidate_count(),
+        ));
+    }
+    game_state.last_error.is_none()
+}
+
+/// Plays the whole game with the solver on the board, waiting `delay` between guesses.
+/// The keys of [`Playback::handle`] control the playback. The guesses are added to `log`.
+pub fn watch(
+    mut game_state: GameState,
+    delay: Duration,
+    confidence: bool,
+    mut log: Option<&mut Vec<Decision>>,
+) -> Result<(), RustleError> {
+    let mut stdout = stdout().into_raw_mode().map_err(RustleError::Terminal)?;
+    let tty = termion::get_tty().map_err(RustleError::Terminal)?;
+    let mut keys = tty.try_clone().map_err(RustleError::Terminal)?.keys();
+    let mut playback = Playback::new(delay);
+    game_state.animate = true;
+    let mut interrupted = false;
+    while !game_state.is_over() {
+        render(&mut stdout, &game_state);
+        // the board starts right below, so both share the line above it
+        let confidence = if confidence {
+            let remaining = game_state.remaining_candidate_count();
+            format!("{} {} left  ", confidence_bar(remaining, 10), remaining)
+        } else {
+            String::new()
+        };
+        write!(
+            stdout,
+            "{}{}{}",
+            termion::cursor::Goto(BOARD_LEFT, 2),
+            confidence,
+            playback.status()
+        )
+        .unwrap();
+        stdout.flush().unwrap();
+        // while paused only keys move the game on
+        let timeout = if playback.paused {
+            Duration::from_secs(3600)
+        } else {
+            playback.delay
+        };
+        if timer::wait_for_input(&tty, timeout) {
+            let control = match keys.next() {
+                Some(Ok(key)) => playback.handle(key),
+                _ => Control::Stop,
+            };
+            match control {
+                Control::Continue => continue,
+                Control::Step => (),
+                Control::Stop => {
+                    interrupted = true;
+                    break;
+                }
+            }
+        } else if playback.paused {
+            continue;
+        }
+        if !step(&mut game_state, log.as_deref_mut()) {
+            break;
+        }
+        animate_reveal(&mut stdout, &mut game_state);
+    }
+    render(&mut stdout, &game_state);
+    write!(stdout, "{}", termion::cursor::Show).unwrap();
+    let message = if interrupted {
+        format!("Stopped, the word was: {}", game_state.word)
+    } else if game_state.won() {
+        format!("Solved in {} guesses", game_state.guesses.len())
+    } else {
+        format!("The solver didn't find the word: {}", game_state.word)
+    };
+    write!(stdout, "{}\r\n", message).unwrap();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_playback_speed_is_clamped() {
+        let mut playback = Playback::new(Duration::from_millis(1000));
+        for _ in 0..10 {
+            playback.handle(Key::Char('+'));
+        }
+        assert_eq!(playback.delay, MIN_DELAY);
+        for _ in 0..10 {
+            playback.handle(Key::Char('-'));
+        }
+        assert_eq!(playback.delay, MAX_DELAY);
+        assert_eq!(Playback::new(Duration::ZERO).delay, MIN_DELAY);
+        assert_eq!(Playback::new(Duration::from_secs(60)).delay, MAX_DELAY);
+    }
+
+    #[test]
+    fn test_confidence_bar() {
+        assert_eq!(confidence_bar(2315, 10), "[..........]");
+        assert_eq!(confidence_bar(0, 10), "[..........]");
+        assert_eq!(confidence_bar(4, 10), "[###.......]");
+        assert_eq!(confidence_bar(2, 10), "[#####.....]");
+        assert_eq!(confidence_bar(1, 10), "[##########]");
+    }
+
+    #[test]
+    fn test_playback_keys() {
+        let mut playback = Playback::new(Duration::from_millis(1000));
+        assert_eq!(playback.handle(Key::Char(' ')), Control::Continue);
+        assert!(playback.paused);
+        assert!(playback.status().contains("paused"));
+        // stepping works while paused
+        assert_eq!(playback.handle(Key::Char('n')), Control::Step);
+        assert!(playback.paused);
+        assert_eq!(playback.handle(Key::Char('q')), Control::Stop);
+    }
+
+    #[test]
+    fn test_autoplay_solves_within_max_tries() {
+        let words: Vec<String> = [
+            "hello", "jolly", "world", "hells", "belly", "cello", "fuzzy", "crane", "slate",
+        ]
+        .iter()
+        .map(|w| w.to_string())
+        .collect();
+        for answer in &words {
+            let mut game_state = GameState::new(answer.clone(), words.clone(), false);
+            while !game_state.is_over() {
+                assert!(step(&mut game_state, None));
+            }
+            assert!(game_state.won(), "{} was not solved", answer);
+            assert!(game_state.guesses.len() <= game_state.max_tries as usize);
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use crate::config::Config;
+use std::io::Write;
+use termion::event::Key;
+
+const THEMES: [&str; 5] = ["auto", "dark", "colorblind", "light", "no-color"];
+const MIN_LENGTH: usize = 3;
+const MAX_LENGTH: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Theme,
+    WordLength,
+    HardMode,
+    Done,
+}
+
+/// Questions asked on the first start, answered one after another.
+pub struct Wizard {
+    pub step: Step,
+    pub config: Config,
+}
+
+impl Wizard {
+    pub fn new() -> Wizard {
+        Wizard {
+            step: Step::Theme,
+            config: Config::default(),
+        }
+    }
+
+    /// Left and right change the answer, Enter goes to the next question
+    /// and Esc accepts the defaults for everything.
+    pub fn handle(&mut self, key: Key) {
+        match key {
+            Key::Esc => {
+                self.config = Config::default();
+                self.step = Step::Done;
+            }
+            Key::Char('\n') => {
+                self.step = match self.step {
+                    Step::Theme => Step::WordLength,
+                    Step::WordLength => Step::HardMode,
+                    Step::HardMode | Step::Done => Step::Done,
+                }
+            }
+            Key::Left => self.change(-1),
+            Key::Right | Key::Char(' ') => self.change(1),
+            _ => (),
+        }
+    }
+
+    fn change(&mut self, direction: isize) {
+        match self.step {
+            Step::Theme => {
+                let i = THEMES
+                    .iter()
+                    .position(|theme| *theme == self.config.theme)
+                    .unwrap_or(0) as isize;
+                let next = (i + direction).rem_euclid(THEMES.len() as isize);
+                self.config.theme = THEMES[next as usize].to_string();
+            }
+            Step::WordLength => {
+                let length = self.config.word_length as isize + direction;
+                self.config.word_length =
+                    length.clamp(MIN_LENGTH as isize, MAX_LENGTH as isize) as usize;
+            }
+            Step::HardMode => self.config.hard_mode = !self.config.hard_mode,
+            Step::Done => (),
+        }
+    }
+
+    pub fn question(&self) -> Option<String> {
+        match self.step {
+            Step::Theme => Some(format!("Color theme: < {} >", self.config.theme)),
+            Step::WordLength => Some(format!("Word length: < {} >", self.config.word_length)),
+            Step::HardMode => Some(format!(
+                "Hard mode: < {} >",
+                if self.config.hard_mode { "on" } else { "off" }
+            )),
+            Step::Done => None,
+        }
+    }
+}
+
+/// Asks the questions in the terminal and returns the resulting config.
+pub fn run() -> Config {
+    use std::io::{stdin, stdout};
+    use termion::input::TermRead;
+    use termion::raw::IntoRawMode;
+
+    let mut stdout = stdout().into_raw_mode().unwrap();
+    let mut wizard = Wizard::new();
+    let mut keys = stdin().keys();
+    while let Some(question) = wizard.question() {
+        write!(
+            stdout,
+            "{}{}Welcome to rustle! Let's set up your preferences.\r\n\
+             Use left/right to change, Enter to continue, Esc to skip.\r\n\r\n{}\r\n",
+            termion::clear::All,
+            termion::cursor::Goto(1, 1),
+            question
+        )
+        .unwrap();
+        stdout.flush().unwrap();
+        match keys.next() {
+            Some(Ok(key)) => wizard.handle(key),
+            _ => break,
+        }
+    }
+    write!(
+        stdout,
+        "{}{}",
+        termion::clear::All,
+        termion::cursor::Goto(1, 1)
+    )
+    .unwrap();
+    stdout.flush().unwrap();
+    wizard.config
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_questions_in_order() {
+        let mut wizard = Wizard::new();
+        assert_eq!(wizard.step, Step::Theme);
+        wizard.handle(Key::Right);
+        wizard.handle(Key::Char('\n'));
+        assert_eq!(wizard.step, Step::WordLength);
+        wizard.handle(Key::Right);
+        wizard.handle(Key::Char('\n'));
+        assert_eq!(wizard.step, Step::HardMode);
+        wizard.handle(Key::Right);
+        wizard.handle(Key::Char('\n'));
+        assert_eq!(wizard.step, Step::Done);
+        assert_eq!(
+            wizard.config,
+            Config {
+                theme: "dark".to_string(),
+                word_length: 6,
+                hard_mode: true,
+                keyboard_legend: true,
+                available_letters: false,
+                eliminated_letters: false,
+                tries_pips: false,
+                keyboard_layout: Vec::new(),
+                header: true,
+                ignore_partial_enter: false,
+                keep_invalid_input: false,
+                suggest_words: false,
+                screensaver_seconds: 0,
+                cold_streak: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn test_escape_accepts_defaults() {
+        let mut wizard = Wizard::new();
+        wizard.handle(Key::Right);
+        wizard.handle(Key::Char('\n'));
+        wizard.handle(Key::Esc);
+        assert_eq!(wizard.step, Step::Done);
+        assert_eq!(wizard.config, Config::default());
+        assert_eq!(wizard.question(), None);
+    }
+
+    #[test]
+    fn test_answers_wrap_and_clamp() {
+        let mut wizard = Wizard::new();
+        wizard.handle(Key::Left);
+        assert_eq!(wizard.config.theme, "no-color");
+        wizard.handle(Key::Char('\n'));
+        for _ in 0..20 {
+            wizard.handle(Key::Right);
+        }
+        assert_eq!(wizard.config.word_length, MAX_LENGTH);
+    }
+}
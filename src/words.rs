@@ -0,0 +1,747 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// Words which may be picked as the answer.
+pub const PICKED_WORDS: &str = include_str!("../data/picked_words.txt");
+/// All other words accepted as guesses.
+pub const VALID_WORDS: &str = include_str!("../data/valid_words.txt");
+
+/// Number of words in the embedded word lists.
+pub fn embedded_word_count() -> usize {
+    PICKED_WORDS.lines().count() + VALID_WORDS.lines().count()
+}
+
+/// Reads all of `reader`, failing with the position of the first byte which isn't UTF-8.
+fn read_utf8<R: Read>(mut reader: R) -> io::Result<String> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    String::from_utf8(bytes).map_err(|error| {
+        let valid = &error.as_bytes()[..error.utf8_error().valid_up_to()];
+        let line = valid.iter().filter(|b| **b == b'\n').count() + 1;
+        let column = valid.iter().rev().take_while(|b| **b != b'\n').count() + 1;
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "not valid UTF-8 (invalid byte at line {}, column {}), \
+                 convert it first, e.g. with `iconv -f latin1 -t utf-8`",
+                line, column
+            ),
+        )
+    })
+}
+
+/// Adds the path of the word list to `error`.
+fn with_path(path: &Path, error: io::Error) -> io::Error {
+    io::Error::new(error.kind(), format!("{}: {}", path.display(), error))
+}
+
+/// Reads a newline separated word list.
+/// Words are trimmed and lowercased, empty lines are skipped.
+pub fn load_words<R: Read>(reader: R) -> io::Result<Vec<String>> {
+    let contents = read_utf8(reader)?;
+    Ok(contents
+        .split('\n')
+        .map(|s| s.trim().to_lowercase())
+        .filter(|s| !s.is_empty())
+        .collect())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IssueKind {
+    EmptyLine,
+    Duplicate,
+    NonAlphabetic,
+    MixedLength,
+}
+
+impl std::fmt::Display for IssueKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            IssueKind::EmptyLine => write!(f, "Empty lines"),
+            IssueKind::Duplicate => write!(f, "Duplicates"),
+            IssueKind::NonAlphabetic => write!(f, "Non-alphabetic characters"),
+            IssueKind::MixedLength => write!(f, "Unusual length"),
+        }
+    }
+}
+
+/// Problem found on a line of a word list, lines are counted from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    pub line: usize,
+    pub kind: IssueKind,
+    pub text: String,
+}
+
+/// Result of checking a word list, see `validate_words`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Validation {
+    pub words: usize,
+    /// most common word length
+    pub length: Option<usize>,
+    pub issues: Vec<Issue>,
+}
+
+impl Validation {
+    pub fn count(&self, kind: IssueKind) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| issue.kind == kind)
+            .count()
+    }
+
+    /// Counts of every kind of issue with the first `examples` offending lines.
+    pub fn report(&self, examples: usize) -> String {
+        let mut report = format!("{} words", self.words);
+        if let Some(length) = self.length {
+            report.push_str(&format!(", most have {} letters", length));
+        }
+        if self.issues.is_empty() {
+            report.push_str("\nNo problems found");
+        }
+        for kind in [
+            IssueKind::EmptyLine,
+            IssueKind::Duplicate,
+            IssueKind::NonAlphabetic,
+            IssueKind::MixedLength,
+        ] {
+            let count = self.count(kind);
+            if count == 0 {
+                continue;
+            }
+            report.push_str(&format!("\n{}: {}", kind, count));
+            for issue in self
+                .issues
+                .iter()
+                .filter(|issue| issue.kind == kind)
+                .take(examples)
+            {
+                report.push_str(&format!("\n  line {}: {:?}", issue.line, issue.text));
+            }
+        }
+        report
+    }
+}
+
+/// Reads a word list like `load_words`, but reports the lines it would skip or
+/// which are likely mistakes instead of silently ignoring them.
+pub fn validate_words<R: Read>(reader: R) -> io::Result<Validation> {
+    let contents = read_utf8(reader)?;
+    let mut issues = Vec::new();
+    let mut words: Vec<(usize, String)> = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    for (i, line) in contents.lines().enumerate() {
+        let mut word = line.trim().to_lowercase();
+        if let Some((annotated, frequency)) = word.split_once(char::is_whitespace) {
+            if frequency.trim().parse::<f64>().is_ok() {
+                word = annotated.to_string();
+            }
+        }
+        let issue = |kind| Issue {
+            line: i + 1,
+            kind,
+            text: line.to_string(),
+        };
+        if word.is_empty() {
+            issues.push(issue(IssueKind::EmptyLine));
+            continue;
+        }
+        if !seen.insert(word.clone()) {
+            issues.push(issue(IssueKind::Duplicate));
+        }
+        if !word.chars().all(char::is_alphabetic) {
+            issues.push(issue(IssueKind::NonAlphabetic));
+        }
+        words.push((i + 1, word));
+    }
+
+    let mut lengths: HashMap<usize, usize> = HashMap::new();
+    for (_, word) in &words {
+        *lengths.entry(word.chars().count()).or_insert(0) += 1;
+    }
+    // ties go to the shorter length so reports are stable
+    let length = lengths
+        .iter()
+        .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+        .map(|(length, _)| *length);
+    for (line, word) in &words {
+        if Some(word.chars().count()) != length {
+            issues.push(Issue {
+                line: *line,
+                kind: IssueKind::MixedLength,
+                text: word.clone(),
+            });
+        }
+    }
+    issues.sort_by_key(|issue| (issue.kind, issue.line));
+    Ok(Validation {
+        words: words.len(),
+        length,
+        issues,
+    })
+}
+
+/// Vowels of the supported languages, including accented ones.
+const VOWELS: &str = "aeiouäöüáàâéèêíìîóòôúùûåæøœ";
+
+/// Number of vowels in `word`, repeated vowels are counted every time.
+pub fn count_vowels(word: &str) -> usize {
+    word.chars()
+        .flat_map(char::to_lowercase)
+        .filter(|c| VOWELS.contains(*c))
+        .count()
+}
+
+/// Splits off the frequencies of an annotated word list, lines like "hello 0.42".
+/// Words without a frequency are kept as they are.
+pub fn split_frequencies(words: Vec<String>) -> (Vec<String>, HashMap<String, f64>) {
+    let mut frequencies = HashMap::new();
+    let words = words
+        .into_iter()
+        .map(|line| match line.split_once(char::is_whitespace) {
+            Some((word, frequency)) => match frequency.trim().parse::<f64>() {
+                Ok(frequency) => {
+                    frequencies.insert(word.to_string(), frequency);
+                    word.to_string()
+                }
+                Err(_) => line,
+            },
+            None => line,
+        })
+        .collect();
+    (words, frequencies)
+}
+
+/// Drops the words with a frequency below `min` per million.
+/// Words without a frequency are kept, without any frequencies all words are.
+pub fn filter_min_frequency(
+    words: Vec<String>,
+    frequencies: &HashMap<String, f64>,
+    min: f64,
+) -> Vec<String> {
+    words
+        .into_iter()
+        .filter(|w| {
+            frequencies
+                .get(w)
+                .map_or(true, |frequency| *frequency >= min)
+        })
+        .collect()
+}
+
+/// Reads a word list from a gzip compressed stream.
+#[cfg(feature = "gzip")]
+pub fn load_gzip_words<R: Read>(reader: R) -> io::Result<Vec<String>> {
+    let mut bytes = Vec::new();
+    flate2::read::GzDecoder::new(reader)
+        .read_to_end(&mut bytes)
+        .map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("corrupt gzip data: {}", e),
+            )
+        })?;
+    load_words(bytes.as_slice())
+}
+
+/// Adds the `favorites` with the length of one of the `answers` which aren't answers already.
+/// Returns the number of added words.
+pub fn merge_favorites(answers: &mut Vec<String>, favorites: &[String]) -> usize {
+    let lengths: std::collections::HashSet<usize> =
+        answers.iter().map(|w| w.chars().count()).collect();
+    let before = answers.len();
+    for favorite in favorites {
+        if lengths.contains(&favorite.chars().count()) && !answers.contains(favorite) {
+            answers.push(favorite.clone());
+        }
+    }
+    answers.len() - before
+}
+
+/// Keeps only the words made up solely of letters from `alphabet`.
+pub fn filter_alphabet(words: Vec<String>, alphabet: &str) -> Vec<String> {
+    let alphabet = alphabet.to_lowercase();
+    words
+        .into_iter()
+        .filter(|word| word.chars().all(|c| alphabet.contains(c)))
+        .collect()
+}
+
+/// Checks whether any letter occurs more than once in `word`.
+pub fn has_repeated_letters(word: &str) -> bool {
+    let mut seen = std::collections::HashSet::new();
+    !word.chars().all(|c| seen.insert(c))
+}
+
+/// Checks whether `b` uses exactly the letters of `a` in another order.
+pub fn is_anagram(a: &str, b: &str) -> bool {
+    let sorted = |word: &str| {
+        let mut letters: Vec<char> = word.chars().collect();
+        letters.sort_unstable();
+        letters
+    };
+    a != b && sorted(a) == sorted(b)
+}
+
+/// Levenshtein distance between `a` and `b`, None once it is certain to exceed `max`.
+pub fn edit_distance(a: &str, b: &str, max: usize) -> Option<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        // distances only grow from row to row
+        if current.iter().min().is_some_and(|min| *min > max) {
+            return None;
+        }
+        previous = current;
+    }
+    Some(previous[b.len()]).filter(|distance| *distance <= max)
+}
+
+fn is_gzip(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "gz")
+}
+
+/// Loads the word list at `path`, decompressing it if it ends in `.gz`.
+pub fn load_word_file(path: &str) -> io::Result<Vec<String>> {
+    let path = Path::new(path);
+    let file = File::open(path).map_err(|error| with_path(path, error))?;
+    if is_gzip(path) {
+        #[cfg(feature = "gzip")]
+        return load_gzip_words(file).map_err(|error| with_path(path, error));
+        #[cfg(not(feature = "gzip"))]
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "gzip word lists require the `gzip` feature",
+        ));
+    }
+    load_words(file).map_err(|error| with_path(path, error))
+}
+
+/// Source of a word list, e.g. a file or the embedded lists.
+pub trait WordProvider {
+    /// Short name of the source shown in the header, e.g. the file name.
+    fn name(&self) -> String;
+    /// Loads the words, lines may still carry a frequency like "hello 0.42".
+    fn words(&self) -> io::Result<Vec<String>>;
+    /// Words which are accepted as guesses, but never picked as the answer.
+    fn extra_guesses(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// Word list read from a file with `load_word_file`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileWords {
+    pub path: String,
+}
+
+impl WordProvider for FileWords {
+    fn name(&self) -> String {
+        Path::new(&self.path)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.path.clone())
+    }
+
+    fn words(&self) -> io::Result<Vec<String>> {
+        load_word_file(&self.path)
+    }
+}
+
+/// Name of the embedded lists where a word file is expected.
+pub const EMBEDDED_SOURCE: &str = "embedded";
+
+/// The embedded answer list, the other embedded words are only accepted as guesses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmbeddedWords;
+
+impl WordProvider for EmbeddedWords {
+    fn name(&self) -> String {
+        "English".to_string()
+    }
+
+    fn words(&self) -> io::Result<Vec<String>> {
+        load_words(PICKED_WORDS.as_bytes())
+    }
+
+    fn extra_guesses(&self) -> Vec<String> {
+        VALID_WORDS.lines().map(|l| l.to_lowercase()).collect()
+    }
+}
+
+/// Provider of a word source given on the command line, a path or `embedded`.
+pub fn provider(source: &str) -> Box<dyn WordProvider> {
+    if source == EMBEDDED_SOURCE {
+        Box::new(EmbeddedWords)
+    } else {
+        Box::new(FileWords {
+            path: source.to_string(),
+        })
+    }
+}
+
+/// How a `CompositeWordProvider` combines its providers.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CompositePolicy {
+    /// all words of every provider, the first occurrence of a word wins
+    Merge,
+    /// the words of the first provider which loads and isn't empty
+    #[default]
+    Fallback,
+}
+
+impl std::str::FromStr for CompositePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<CompositePolicy, String> {
+        match s {
+            "merge" => Ok(CompositePolicy::Merge),
+            "fallback" => Ok(CompositePolicy::Fallback),
+            _ => Err(format!("unknown word source policy \"{}\"", s)),
+        }
+    }
+}
+
+/// Words loaded by a `CompositeWordProvider`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordList {
+    /// names of the providers the words came from
+    pub name: String,
+    pub words: Vec<String>,
+    /// only accepted as guesses
+    pub extra_guesses: Vec<String>,
+}
+
+/// Providers tried in order, e.g. a user file, then a pack, then the embedded list.
+pub struct CompositeWordProvider {
+    pub providers: Vec<Box<dyn WordProvider>>,
+    pub policy: CompositePolicy,
+}
+
+impl CompositeWordProvider {
+    pub fn new(providers: Vec<Box<dyn WordProvider>>, policy: CompositePolicy) -> Self {
+        CompositeWordProvider { providers, policy }
+    }
+
+    /// Providers of the word sources given on the command line.
+    pub fn for_sources(sources: &[String], policy: CompositePolicy) -> Self {
+        let providers = sources.iter().map(|source| provider(source)).collect();
+        CompositeWordProvider::new(providers, policy)
+    }
+
+    /// Loads the words of the providers.
+    /// Merging fails on the first provider which fails, falling back only if all do.
+    pub fn resolve(&self) -> io::Result<WordList> {
+        match self.policy {
+            CompositePolicy::Merge => {
+                let mut seen = std::collections::HashSet::new();
+                let mut words = Vec::new();
+                let mut extra_guesses = Vec::new();
+                for provider in &self.providers {
+                    for line in provider.words()? {
+                        // a word annotated with a frequency is the same word
+                        let word = line.split_whitespace().next().unwrap_or("").to_string();
+                        if seen.insert(word) {
+                            words.push(line);
+                        }
+                    }
+                    extra_guesses.extend(provider.extra_guesses());
+                }
+                extra_guesses.retain(|word| seen.insert(word.clone()));
+                let names: Vec<String> = self.providers.iter().map(|p| p.name()).collect();
+                Ok(WordList {
+                    name: names.join("+"),
+                    words,
+                    extra_guesses,
+                })
+            }
+            CompositePolicy::Fallback => {
+                let mut last_error = None;
+                for provider in &self.providers {
+                    match provider.words() {
+                        Ok(words) if !words.is_empty() => {
+                            return Ok(WordList {
+                                name: provider.name(),
+                                words,
+                                extra_guesses: provider.extra_guesses(),
+                            })
+                        }
+                        Ok(_) => {}
+                        Err(error) => last_error = Some(error),
+                    }
+                }
+                Err(last_error.unwrap_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidData, "word list is empty")
+                }))
+            }
+        }
+    }
+}
+
+/// Checks the word list at `path` with `validate_words`.
+pub fn validate_word_file(path: &str) -> io::Result<Validation> {
+    let path = Path::new(path);
+    let file = File::open(path).map_err(|error| with_path(path, error))?;
+    if is_gzip(path) {
+        #[cfg(feature = "gzip")]
+        return validate_words(flate2::read::GzDecoder::new(file))
+            .map_err(|error| with_path(path, error));
+        #[cfg(not(feature = "gzip"))]
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "gzip word lists require the `gzip` feature",
+        ));
+    }
+    validate_words(file).map_err(|error| with_path(path, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_words() {
+        let words = load_words(" Gold\nKatze \n\nhällö\n".as_bytes()).unwrap();
+        assert_eq!(words, vec!["gold", "katze", "hällö"]);
+    }
+
+    #[test]
+    fn test_invalid_utf8_is_reported() {
+        let error = load_words(&b"hello\nw\xf6rld\n"[..]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.to_string().contains("line 2, column 2"));
+
+        let path = std::env::temp_dir().join(format!("rustle-latin1-{}.txt", std::process::id()));
+        std::fs::write(&path, b"gr\xfcn\n").unwrap();
+        let error = load_word_file(path.to_str().unwrap()).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert!(error.to_string().contains(&path.display().to_string()));
+        assert!(error.to_string().contains("not valid UTF-8"));
+    }
+
+    #[test]
+    fn test_validate_flawed_list() {
+        let list = "hello\nworld\n\nHello\nj0lly\nhi\nfancy\n";
+        let validation = validate_words(list.as_bytes()).unwrap();
+        assert_eq!(validation.words, 6);
+        assert_eq!(validation.length, Some(5));
+        assert_eq!(validation.count(IssueKind::EmptyLine), 1);
+        assert_eq!(validation.count(IssueKind::Duplicate), 1);
+        assert_eq!(validation.count(IssueKind::NonAlphabetic), 1);
+        assert_eq!(validation.count(IssueKind::MixedLength), 1);
+        let lines: Vec<(IssueKind, usize)> = validation
+            .issues
+            .iter()
+            .map(|issue| (issue.kind, issue.line))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                (IssueKind::EmptyLine, 3),
+                (IssueKind::Duplicate, 4),
+                (IssueKind::NonAlphabetic, 5),
+                (IssueKind::MixedLength, 6),
+            ]
+        );
+        assert!(validation.report(3).contains("line 5: \"j0lly\""));
+    }
+
+    #[test]
+    fn test_validate_clean_list() {
+        let validation = validate_words("gold\nkatze\nhällö\n".as_bytes()).unwrap();
+        assert_eq!(validation.count(IssueKind::NonAlphabetic), 0);
+        assert_eq!(validation.count(IssueKind::MixedLength), 1);
+        let validation = validate_words("hello\nworld".as_bytes()).unwrap();
+        assert!(validation.issues.is_empty());
+        assert!(validation.report(3).contains("No problems found"));
+    }
+
+    #[test]
+    fn test_split_frequencies() {
+        let words = load_words("hello 0.5\nworld\t12\njolly\n".as_bytes()).unwrap();
+        let (words, frequencies) = split_frequencies(words);
+        assert_eq!(words, vec!["hello", "world", "jolly"]);
+        assert_eq!(frequencies.get("hello"), Some(&0.5));
+        assert_eq!(frequencies.get("world"), Some(&12.0));
+        assert_eq!(frequencies.get("jolly"), None);
+        let validation = validate_words("hello 0.5\nworld 3".as_bytes()).unwrap();
+        assert!(validation.issues.is_empty());
+    }
+
+    #[test]
+    fn test_filter_min_frequency() {
+        let (words, frequencies) =
+            split_frequencies(load_words("hello 5\nxysti 0.1\njolly\n".as_bytes()).unwrap());
+        assert_eq!(
+            filter_min_frequency(words.clone(), &frequencies, 1.0),
+            vec!["hello", "jolly"]
+        );
+        assert_eq!(
+            filter_min_frequency(words.clone(), &HashMap::new(), 1.0),
+            words
+        );
+    }
+
+    #[test]
+    fn test_count_vowels() {
+        assert_eq!(count_vowels("crwth"), 0);
+        assert_eq!(count_vowels("hello"), 2);
+        assert_eq!(count_vowels("queue"), 4);
+        assert_eq!(count_vowels("HÄLLÖ"), 2);
+        assert_eq!(count_vowels("café"), 2);
+    }
+
+    #[test]
+    fn test_merge_favorites() {
+        let mut answers = vec!["hello".to_string(), "world".to_string()];
+        let favorites = load_words("rusty\nhello\ncrab\nferris\nrusty\n".as_bytes()).unwrap();
+        assert_eq!(merge_favorites(&mut answers, &favorites), 1);
+        assert_eq!(answers, vec!["hello", "world", "rusty"]);
+    }
+
+    #[test]
+    fn test_filter_alphabet() {
+        let words = vec![
+            "hello".to_string(),
+            "jolly".to_string(),
+            "holle".to_string(),
+        ];
+        let filtered = filter_alphabet(words, "HELO");
+        assert_eq!(filtered, vec!["hello", "holle"]);
+    }
+
+    #[test]
+    fn test_has_repeated_letters() {
+        assert!(!has_repeated_letters("crane"));
+        assert!(has_repeated_letters("allee"));
+        assert!(has_repeated_letters("apple"));
+    }
+
+    #[test]
+    fn test_is_anagram() {
+        assert!(is_anagram("stale", "least"));
+        assert!(is_anagram("llama", "malla"));
+        assert!(!is_anagram("stale", "stale"));
+        assert!(!is_anagram("hello", "helos"));
+        assert!(!is_anagram("hello", "helo"));
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("hello", "hello", 2), Some(0));
+        assert_eq!(edit_distance("hwllo", "hello", 2), Some(1));
+        assert_eq!(edit_distance("hlelo", "hello", 2), Some(2));
+        assert_eq!(edit_distance("help", "hello", 2), Some(2));
+        assert_eq!(edit_distance("crane", "hello", 2), None);
+        assert_eq!(edit_distance("a", "hello", 2), None);
+    }
+
+    #[test]
+    fn test_filter_alphabet_empty_result() {
+        let words = vec!["hello".to_string()];
+        assert!(filter_alphabet(words, "xyz").is_empty());
+    }
+
+    struct ListWords(&'static str);
+
+    impl WordProvider for ListWords {
+        fn name(&self) -> String {
+            "list".to_string()
+        }
+
+        fn words(&self) -> io::Result<Vec<String>> {
+            load_words(self.0.as_bytes())
+        }
+    }
+
+    #[test]
+    fn test_composite_merges_and_dedups() {
+        let composite = CompositeWordProvider::new(
+            vec![
+                Box::new(ListWords("hello 5\nworld\n")),
+                Box::new(ListWords("world\nhello\njolly\n")),
+            ],
+            CompositePolicy::Merge,
+        );
+        let list = composite.resolve().unwrap();
+        assert_eq!(list.name, "list+list");
+        assert_eq!(list.words, vec!["hello 5", "world", "jolly"]);
+        assert!(list.extra_guesses.is_empty());
+
+        let composite = CompositeWordProvider::new(
+            vec![
+                Box::new(ListWords("zzzzz\nhello\n")),
+                Box::new(EmbeddedWords),
+            ],
+            CompositePolicy::Merge,
+        );
+        let list = composite.resolve().unwrap();
+        assert_eq!(list.words[..2], ["zzzzz", "hello"]);
+        assert_eq!(list.words.len(), PICKED_WORDS.lines().count() + 1);
+        assert!(!list.extra_guesses.iter().any(|w| w == "hello"));
+    }
+
+    #[test]
+    fn test_composite_falls_back() {
+        let missing = std::env::temp_dir().join(format!(
+            "rustle-missing-composite-{}.txt",
+            std::process::id()
+        ));
+        let sources = [
+            missing.to_str().unwrap().to_string(),
+            EMBEDDED_SOURCE.to_string(),
+        ];
+        let composite = CompositeWordProvider::for_sources(&sources, CompositePolicy::Fallback);
+        let list = composite.resolve().unwrap();
+        assert_eq!(list.name, "English");
+        assert_eq!(list.words.len(), PICKED_WORDS.lines().count());
+        assert_eq!(list.extra_guesses.len(), VALID_WORDS.lines().count());
+
+        let composite = CompositeWordProvider::new(
+            vec![Box::new(ListWords("")), Box::new(ListWords("hello\n"))],
+            CompositePolicy::Fallback,
+        );
+        assert_eq!(composite.resolve().unwrap().words, vec!["hello"]);
+    }
+
+    #[test]
+    fn test_is_gzip() {
+        assert!(is_gzip(Path::new("words.txt.gz")));
+        assert!(!is_gzip(Path::new("words.txt")));
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_load_gzip_words() {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+        use std::io::Write;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"hello\nWorld\n").unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let words = load_gzip_words(compressed.as_slice()).unwrap();
+        assert_eq!(words, vec!["hello", "world"]);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_load_gzip_words_corrupt() {
+        let result = load_gzip_words(b"definitely not gzip".as_slice());
+        let error = result.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.to_string().contains("corrupt gzip data"));
+    }
+}